@@ -0,0 +1,280 @@
+//! Runtime version negotiation for the RenderDoc in-application API.
+
+use std::ffi::{CString, c_void};
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+
+use bevy::input::keyboard::KeyCode;
+use bevy::log::warn;
+use renderdoc::{InputButton, OverlayBits, RenderDoc, V100, V110, V112, V120, V140};
+
+use crate::{CaptureOptions, RenderDocOverlaySettings, keys};
+
+pub(crate) use renderdoc::Error;
+
+/// The RenderDoc API, loaded at the newest version supported by both this crate and the attached RenderDoc.
+///
+/// Entry points common to all versions are available through [Deref], while newer ones are wrapped in methods
+/// that degrade gracefully when the attached RenderDoc is too old.
+#[derive(Debug)]
+pub(crate) enum RenderDocApi {
+    V100(RenderDoc<V100>),
+    V110(RenderDoc<V110>),
+    V112(RenderDoc<V112>),
+    V120(RenderDoc<V120>),
+    V140(RenderDoc<V140>),
+}
+
+impl RenderDocApi {
+    /// Load the newest API version available, falling back to older versions one at a time.
+    pub(crate) fn load() -> Result<Self, Error> {
+        RenderDoc::new()
+            .map(Self::V140)
+            .or_else(|_| RenderDoc::new().map(Self::V120))
+            .or_else(|_| RenderDoc::new().map(Self::V112))
+            .or_else(|_| RenderDoc::new().map(Self::V110))
+            .or_else(|_| RenderDoc::new().map(Self::V100))
+    }
+
+    /// Load the API, first loading the RenderDoc library from `library_path` if RenderDoc isn't already loaded into
+    /// the process.
+    pub(crate) fn load_with_library(library_path: Option<&Path>) -> Result<Self, Error> {
+        let error = match Self::load() {
+            Ok(api) => return Ok(api),
+            Err(e) => e,
+        };
+
+        let Some(library_path) = library_path else {
+            return Err(error);
+        };
+
+        // SAFETY: RenderDoc's initialization only installs its graphics API hooks.
+        match unsafe { libloading::Library::new(library_path) } {
+            // RenderDoc must stay loaded for the rest of the process, as its hooks can't be removed.
+            Ok(library) => std::mem::forget(library),
+            Err(e) => {
+                warn!(
+                    "Failed to load the RenderDoc library from {}: {}",
+                    library_path.display(),
+                    e
+                );
+                return Err(error);
+            }
+        }
+
+        Self::load()
+    }
+
+    /// Show the overlay parts enabled in `settings`.
+    pub(crate) fn set_overlay(&mut self, settings: &RenderDocOverlaySettings) {
+        let mut bits = OverlayBits::NONE;
+        bits.set(OverlayBits::ENABLED, settings.enabled);
+        bits.set(OverlayBits::FRAME_RATE, settings.frame_rate);
+        bits.set(OverlayBits::FRAME_NUMBER, settings.frame_number);
+        bits.set(OverlayBits::CAPTURE_LIST, settings.capture_list);
+        self.mask_overlay_bits(OverlayBits::NONE, bits);
+    }
+
+    /// Apply the capture options set, warning about any the attached RenderDoc doesn't support.
+    pub(crate) fn apply_capture_options(&mut self, options: &CaptureOptions) {
+        let flags = [
+            (
+                "ApiValidation",
+                renderdoc_sys::eRENDERDOC_Option_APIValidation,
+                options.api_validation,
+            ),
+            (
+                "CaptureCallstacks",
+                renderdoc_sys::eRENDERDOC_Option_CaptureCallstacks,
+                options.capture_callstacks,
+            ),
+            (
+                "CaptureCallstacksOnlyDraws",
+                renderdoc_sys::eRENDERDOC_Option_CaptureCallstacksOnlyDraws,
+                options.capture_callstacks_only_draws,
+            ),
+            (
+                "RefAllResources",
+                renderdoc_sys::eRENDERDOC_Option_RefAllResources,
+                options.ref_all_resources,
+            ),
+            (
+                "VerifyMapWrites",
+                renderdoc_sys::eRENDERDOC_Option_VerifyMapWrites,
+                options.verify_buffer_access,
+            ),
+            (
+                "AllowVSync",
+                renderdoc_sys::eRENDERDOC_Option_AllowVSync,
+                options.allow_vsync,
+            ),
+            (
+                "AllowFullscreen",
+                renderdoc_sys::eRENDERDOC_Option_AllowFullscreen,
+                options.allow_fullscreen,
+            ),
+            (
+                "DebugOutputMute",
+                renderdoc_sys::eRENDERDOC_Option_DebugOutputMute,
+                options.debug_output_mute,
+            ),
+            (
+                "HookIntoChildren",
+                renderdoc_sys::eRENDERDOC_Option_HookIntoChildren,
+                options.hook_into_children,
+            ),
+            (
+                "CaptureAllCmdLists",
+                renderdoc_sys::eRENDERDOC_Option_CaptureAllCmdLists,
+                options.capture_all_cmd_lists,
+            ),
+        ];
+        let flags = flags
+            .into_iter()
+            .map(|(name, option, value)| (name, option, value.map(u32::from)));
+
+        let soft_memory_limit = (
+            "SoftMemoryLimit",
+            renderdoc_sys::eRENDERDOC_Option_SoftMemoryLimit,
+            options.soft_memory_limit,
+        );
+
+        for (name, option, value) in flags.chain([soft_memory_limit]) {
+            let Some(value) = value else {
+                continue;
+            };
+
+            if !self.set_capture_option(option, value) {
+                warn!(
+                    "The attached RenderDoc does not support the {} capture option, ignoring it",
+                    name
+                );
+            }
+        }
+    }
+
+    /// Unbind RenderDoc's own capture and focus toggle hotkeys.
+    pub(crate) fn disable_hotkeys(&mut self) {
+        self.set_capture_keys::<InputButton>(&[]);
+        self.set_focus_toggle_keys::<InputButton>(&[]);
+    }
+
+    /// Bind RenderDoc's focus toggle hotkeys, warning about and skipping keys RenderDoc can't bind.
+    pub(crate) fn bind_focus_toggle_keys(&mut self, keys: &[KeyCode]) {
+        let buttons: Vec<_> = keys
+            .iter()
+            .filter_map(|&key| {
+                let button = keys::input_button(key);
+                if button.is_none() {
+                    warn!(
+                        "RenderDoc can't bind {:?} as a focus toggle key, skipping it",
+                        key
+                    );
+                }
+                button
+            })
+            .collect();
+        self.set_focus_toggle_keys(&buttons);
+    }
+
+    /// Set the comments stored in the capture file at `path`.
+    ///
+    /// Returns `false` if the attached RenderDoc is older than 1.2 and does not support capture comments.
+    pub(crate) fn set_capture_file_comments(&mut self, path: &Path, comments: &str) -> bool {
+        let path = path.to_str();
+
+        match self {
+            Self::V120(api) => api.set_capture_file_comments(path, comments),
+            Self::V140(api) => api.set_capture_file_comments(path, comments),
+            Self::V100(_) | Self::V110(_) | Self::V112(_) => return false,
+        }
+
+        true
+    }
+
+    /// Set a capture option by its raw identifier, which also covers options newer than the `renderdoc` crate.
+    ///
+    /// Returns `false` if the attached RenderDoc does not support the option or the value.
+    pub(crate) fn set_capture_option(
+        &mut self,
+        option: renderdoc_sys::RENDERDOC_CaptureOption,
+        value: u32,
+    ) -> bool {
+        // SAFETY: `SetCaptureOptionU32` is present in every API version, and rejects options it doesn't know.
+        unsafe {
+            match (*self.raw_api()).SetCaptureOptionU32 {
+                Some(set_capture_option) => set_capture_option(option, value) == 1,
+                None => false,
+            }
+        }
+    }
+
+    /// Discard the capture in progress without saving it.
+    ///
+    /// Returns `false` if the attached RenderDoc is older than 1.4 and does not support discarding captures.
+    pub(crate) fn discard_frame_capture(
+        &mut self,
+        device: *const c_void,
+        window: *const c_void,
+    ) -> bool {
+        match self {
+            Self::V140(api) => {
+                api.discard_frame_capture(device, window);
+                true
+            }
+            Self::V100(_) | Self::V110(_) | Self::V112(_) | Self::V120(_) => false,
+        }
+    }
+
+    /// Set the title of the capture in progress.
+    ///
+    /// Returns `false` if the attached RenderDoc is older than 1.6 and does not support capture titles.
+    pub(crate) fn set_capture_title(&mut self, title: &str) -> bool {
+        if self.get_api_version() < (1, 6, 0) {
+            return false;
+        }
+
+        let Ok(title) = CString::new(title) else {
+            return false;
+        };
+
+        // SAFETY: RenderDoc hands out its newest API table regardless of the version requested, so the 1.6 entry
+        // points are present once it reports version 1.6 or newer.
+        unsafe {
+            let raw = self.raw_api().cast::<renderdoc_sys::RENDERDOC_API_1_6_0>();
+            match (*raw).SetCaptureTitle {
+                Some(set_capture_title) => {
+                    set_capture_title(title.as_ptr());
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+}
+
+impl Deref for RenderDocApi {
+    type Target = RenderDoc<V100>;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::V100(api) => api,
+            Self::V110(api) => api,
+            Self::V112(api) => api,
+            Self::V120(api) => api,
+            Self::V140(api) => api,
+        }
+    }
+}
+
+impl DerefMut for RenderDocApi {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::V100(api) => api,
+            Self::V110(api) => api,
+            Self::V112(api) => api,
+            Self::V120(api) => api,
+            Self::V140(api) => api,
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Stand-in for the RenderDoc API on platforms RenderDoc doesn't support, or when the `renderdoc` feature is
+//! disabled, so the rest of the plugin compiles unchanged.
+
+use std::ffi::c_void;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use bevy::input::keyboard::KeyCode;
+
+use crate::{CaptureOptions, RenderDocOverlaySettings};
+
+/// The error returned when loading RenderDoc without RenderDoc support.
+#[derive(Debug)]
+pub(crate) struct Error;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RenderDoc support is not compiled in")
+    }
+}
+
+/// The RenderDoc API, which can never be loaded without RenderDoc support.
+#[derive(Debug)]
+pub(crate) enum RenderDocApi {}
+
+impl RenderDocApi {
+    pub(crate) fn load_with_library(_library_path: Option<&Path>) -> Result<Self, Error> {
+        Err(Error)
+    }
+
+    pub(crate) fn get_api_version(&self) -> (u32, u32, u32) {
+        match *self {}
+    }
+
+    pub(crate) fn get_log_file_path_template(&self) -> &Path {
+        match *self {}
+    }
+
+    pub(crate) fn set_log_file_path_template<P: Into<PathBuf>>(&mut self, _path_template: P) {
+        match *self {}
+    }
+
+    pub(crate) fn get_num_captures(&self) -> u32 {
+        match *self {}
+    }
+
+    pub(crate) fn get_capture(&self, _index: u32) -> Option<(PathBuf, SystemTime)> {
+        match *self {}
+    }
+
+    pub(crate) fn is_remote_access_connected(&self) -> bool {
+        match *self {}
+    }
+
+    pub(crate) fn launch_replay_ui(
+        &self,
+        _connect_target_control: bool,
+        _cmd_line: &str,
+    ) -> Result<u32, Error> {
+        match *self {}
+    }
+
+    pub(crate) fn unload_crash_handler(&mut self) {
+        match *self {}
+    }
+
+    pub(crate) fn start_frame_capture(&mut self, _device: *const c_void, _window: *const c_void) {
+        match *self {}
+    }
+
+    pub(crate) fn end_frame_capture(&mut self, _device: *const c_void, _window: *const c_void) {
+        match *self {}
+    }
+
+    pub(crate) fn set_overlay(&mut self, _settings: &RenderDocOverlaySettings) {
+        match *self {}
+    }
+
+    pub(crate) fn apply_capture_options(&mut self, _options: &CaptureOptions) {
+        match *self {}
+    }
+
+    pub(crate) fn disable_hotkeys(&mut self) {
+        match *self {}
+    }
+
+    pub(crate) fn bind_focus_toggle_keys(&mut self, _keys: &[KeyCode]) {
+        match *self {}
+    }
+
+    pub(crate) fn set_capture_file_comments(&mut self, _path: &Path, _comments: &str) -> bool {
+        match *self {}
+    }
+
+    pub(crate) fn discard_frame_capture(
+        &mut self,
+        _device: *const c_void,
+        _window: *const c_void,
+    ) -> bool {
+        match *self {}
+    }
+
+    pub(crate) fn set_capture_title(&mut self, _title: &str) -> bool {
+        match *self {}
+    }
+}
@@ -0,0 +1,310 @@
+//! The [FrameCaptureBackend] trait captures are taken through, and the RenderDoc backend used by default.
+
+use std::ffi::c_void;
+use std::path::{Path, PathBuf};
+use std::ptr::null;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use bevy::ecs::entity::Entity;
+use bevy::log::warn;
+use bevy::render::renderer::RenderDevice;
+use bevy::render::settings::Backends;
+use raw_window_handle::RawWindowHandle;
+
+use crate::api::RenderDocApi;
+use crate::{CaptureOptions, RenderDocOverlaySettings, RenderDocStatus, target};
+
+/// A frame capture tool taking the captures requested through [RenderDocTrigger](crate::RenderDocTrigger).
+///
+/// The plugin handles triggers, queueing, scheduling and events, and only calls into the backend from the render
+/// world to start and end captures. RenderDoc is used unless another backend is set with
+/// [RenderDocPluginBuilder::capture_backend](crate::RenderDocPluginBuilder::capture_backend).
+///
+/// Only starting and ending captures and reporting the status are required. The other methods default to doing
+/// nothing, for backends without the matching feature.
+pub trait FrameCaptureBackend: Send + 'static {
+    /// Start capturing the frames rendered to `target`, titling the capture `title` if set.
+    fn start_frame_capture(&mut self, target: &CaptureTarget, title: Option<&str>);
+
+    /// End the capture in progress on `target`, returning the file it was saved to, or `None` if it wasn't saved.
+    fn end_frame_capture(&mut self, target: &CaptureTarget) -> Option<CaptureFile>;
+
+    /// Discard the capture in progress on `target` without saving it.
+    ///
+    /// Returns `false` if the backend can't discard captures, in which case the capture is ended and its file
+    /// deleted instead.
+    fn discard_frame_capture(&mut self, _target: &CaptureTarget) -> bool {
+        false
+    }
+
+    /// The status reported through [RenderDocStatus] once the backend is attached.
+    fn status(&self) -> RenderDocStatus;
+
+    /// The name the backend is reported as in [RenderDocStatus::backend], its type name by default.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// The graphics backends the backend can capture, all of them by default.
+    ///
+    /// A warning is logged and [UnsupportedGraphicsBackend](crate::UnsupportedGraphicsBackend) sent if the app renders
+    /// with another one, as its captures would be empty.
+    fn graphics_backends(&self) -> Backends {
+        Backends::all()
+    }
+
+    /// Store `comments` in the saved capture file at `path`.
+    ///
+    /// Returns `false` if the backend doesn't support capture comments.
+    fn set_capture_file_comments(&mut self, _path: &Path, _comments: &str) -> bool {
+        false
+    }
+
+    /// Apply the capture options set through
+    /// [RenderDocTrigger::set_capture_options](crate::RenderDocTrigger::set_capture_options).
+    fn apply_capture_options(&mut self, _options: &CaptureOptions) {}
+
+    /// Show the parts of the in-app overlay enabled in `settings`.
+    fn set_overlay(&mut self, _settings: &RenderDocOverlaySettings) {}
+
+    /// Whether a replay tool is connected to the app, in which case saved captures aren't opened in a new one.
+    fn replay_ui_connected(&self) -> bool {
+        false
+    }
+
+    /// Open the saved capture at `path` in the backend's replay tool.
+    fn open_capture(&mut self, path: &Path) {
+        warn!(
+            "The capture backend has no replay tool to open {} in",
+            path.display()
+        );
+    }
+}
+
+/// The render device and window a capture is taken on.
+#[derive(Clone, Default)]
+pub struct CaptureTarget {
+    /// The render device, or `None` if the renderer isn't set up.
+    pub device: Option<RenderDevice>,
+    /// Handle of the window being captured, or `None` if no window is being rendered, e.g. in headless apps.
+    pub window: Option<RawWindowHandle>,
+    /// The window entity the window handle belongs to.
+    pub window_entity: Option<Entity>,
+}
+
+// SAFETY: The window handle is only used to identify the window, never to access it.
+unsafe impl Send for CaptureTarget {}
+// SAFETY: See above.
+unsafe impl Sync for CaptureTarget {}
+
+/// A capture file saved by a [FrameCaptureBackend].
+#[derive(Debug, Clone)]
+pub struct CaptureFile {
+    /// Index of the capture within the backend's session.
+    pub index: u32,
+    /// Path of the capture file.
+    pub path: PathBuf,
+    /// When the capture was saved.
+    pub timestamp: SystemTime,
+}
+
+/// The default backend, capturing through the RenderDoc in-application API.
+pub(crate) struct RenderDocBackend {
+    /// Shared with [RenderDocApi](crate::RenderDocApi), so apps can call entry points the plugin doesn't wrap.
+    api: Arc<Mutex<RenderDocApi>>,
+    /// Number of captures RenderDoc had saved when the capture in progress started.
+    captures_before: u32,
+    /// The path template to restore once the capture in progress ends, if it was changed to name the capture.
+    restore_path_template: Option<PathBuf>,
+}
+
+impl RenderDocBackend {
+    pub(crate) fn new(api: RenderDocApi) -> Self {
+        Self {
+            api: Arc::new(Mutex::new(api)),
+            captures_before: 0,
+            restore_path_template: None,
+        }
+    }
+
+    /// The API, shared with the app.
+    pub(crate) fn api(&self) -> Arc<Mutex<RenderDocApi>> {
+        self.api.clone()
+    }
+
+    fn restore_path_template(&mut self) {
+        if let Some(template) = self.restore_path_template.take() {
+            self.api
+                .lock()
+                .unwrap()
+                .set_log_file_path_template(template);
+        }
+    }
+}
+
+/// The device and window handles RenderDoc identifies `target` by, where a null handle matches any.
+fn handles(target: &CaptureTarget) -> (*const c_void, *const c_void) {
+    (
+        target
+            .device
+            .as_ref()
+            .map_or(null(), target::device_pointer),
+        target.window.map_or(null(), target::window_handle),
+    )
+}
+
+impl FrameCaptureBackend for RenderDocBackend {
+    fn start_frame_capture(&mut self, target: &CaptureTarget, title: Option<&str>) {
+        let mut api = self.api.lock().unwrap();
+        self.captures_before = api.get_num_captures();
+
+        // Without capture titles, fall back to naming the capture file after the title.
+        if let Some(title) = title {
+            if api.get_api_version() < (1, 6, 0) {
+                let template = api.get_log_file_path_template().to_path_buf();
+                let file_name = title.replace(['/', '\\'], "_");
+                api.set_log_file_path_template(template.with_file_name(file_name));
+                self.restore_path_template = Some(template);
+            }
+        }
+
+        let (device, window) = handles(target);
+        api.start_frame_capture(device, window);
+
+        if let Some(title) = title {
+            api.set_capture_title(title);
+        }
+    }
+
+    fn end_frame_capture(&mut self, target: &CaptureTarget) -> Option<CaptureFile> {
+        let (device, window) = handles(target);
+        self.api.lock().unwrap().end_frame_capture(device, window);
+        self.restore_path_template();
+
+        // The capture has been written by the time `end_frame_capture` returns, so a new capture is ours.
+        let api = self.api.lock().unwrap();
+        let index = api.get_num_captures().checked_sub(1)?;
+        if index < self.captures_before {
+            return None;
+        }
+
+        let (path, timestamp) = api.get_capture(index)?;
+        Some(CaptureFile {
+            index,
+            path,
+            timestamp,
+        })
+    }
+
+    fn discard_frame_capture(&mut self, target: &CaptureTarget) -> bool {
+        let (device, window) = handles(target);
+        if !self
+            .api
+            .lock()
+            .unwrap()
+            .discard_frame_capture(device, window)
+        {
+            return false;
+        }
+
+        self.restore_path_template();
+        true
+    }
+
+    fn status(&self) -> RenderDocStatus {
+        let api = self.api.lock().unwrap();
+        RenderDocStatus {
+            available: true,
+            api_version: Some(api.get_api_version()),
+            library_path: loaded_library_path(),
+            capture_path_template: Some(api.get_log_file_path_template().to_path_buf()),
+            ..Default::default()
+        }
+    }
+
+    fn name(&self) -> &str {
+        "RenderDoc"
+    }
+
+    fn graphics_backends(&self) -> Backends {
+        Backends::VULKAN | Backends::DX12 | Backends::GL
+    }
+
+    fn set_capture_file_comments(&mut self, path: &Path, comments: &str) -> bool {
+        self.api
+            .lock()
+            .unwrap()
+            .set_capture_file_comments(path, comments)
+    }
+
+    fn apply_capture_options(&mut self, options: &CaptureOptions) {
+        self.api.lock().unwrap().apply_capture_options(options);
+    }
+
+    fn set_overlay(&mut self, settings: &RenderDocOverlaySettings) {
+        self.api.lock().unwrap().set_overlay(settings);
+    }
+
+    fn replay_ui_connected(&self) -> bool {
+        self.api.lock().unwrap().is_remote_access_connected()
+    }
+
+    fn open_capture(&mut self, path: &Path) {
+        let args = format!("\"{}\"", path.display());
+        if let Err(e) = self
+            .api
+            .lock()
+            .unwrap()
+            .launch_replay_ui(true, args.as_str())
+        {
+            warn!("Failed to launch the RenderDoc replay UI: {}", e);
+        }
+    }
+}
+
+/// Find the path of the RenderDoc library loaded into this process.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn loaded_library_path() -> Option<PathBuf> {
+    const LIBRARY_NAMES: [&str; 2] = ["librenderdoc.so", "libVkLayer_GLES_RenderDoc.so"];
+
+    let maps = std::fs::read_to_string("/proc/self/maps").ok()?;
+    maps.lines()
+        .filter_map(|line| line.split_whitespace().nth(5))
+        .find(|path| LIBRARY_NAMES.iter().any(|name| path.ends_with(name)))
+        .map(PathBuf::from)
+}
+
+/// Find the path of the RenderDoc library loaded into this process.
+#[cfg(all(windows, feature = "renderdoc"))]
+fn loaded_library_path() -> Option<PathBuf> {
+    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+    use winapi::um::libloaderapi::{GetModuleFileNameW, GetModuleHandleW};
+
+    let name: Vec<u16> = std::ffi::OsStr::new("renderdoc.dll")
+        .encode_wide()
+        .chain(Some(0))
+        .collect();
+    let mut path = [0u16; 1024];
+
+    // SAFETY: `name` is nul-terminated and the module handle is only used to query its file name.
+    let len = unsafe {
+        let module = GetModuleHandleW(name.as_ptr());
+        if module.is_null() {
+            return None;
+        }
+        GetModuleFileNameW(module, path.as_mut_ptr(), path.len() as u32)
+    };
+
+    (len > 0).then(|| std::ffi::OsString::from_wide(&path[..len as usize]).into())
+}
+
+/// Find the path of the RenderDoc library loaded into this process.
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    all(windows, feature = "renderdoc")
+)))]
+fn loaded_library_path() -> Option<PathBuf> {
+    None
+}
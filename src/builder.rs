@@ -0,0 +1,448 @@
+//! Fluent configuration for [RenderDocPlugin].
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    CaptureOptions, CaptureSaved, FrameCaptureBackend, RenderDocOverlaySettings, RenderDocPlugin,
+    RenderDocSystems, SpikeCapture, TouchGesture, TriggerBinding, trigger_on_condition,
+};
+
+/// Builder for [RenderDocPlugin], created with [RenderDocPlugin::builder].
+///
+/// Settings that aren't set keep the same defaults as [RenderDocPlugin::default].
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// # let mut app = App::new();
+/// app.add_plugins(
+///     RenderDocPlugin::builder()
+///         .trigger_key(KeyCode::F12)
+///         .capture_path("captures/my_game")
+///         .build(),
+/// );
+/// ```
+#[derive(Default)]
+pub struct RenderDocPluginBuilder {
+    plugin: RenderDocPlugin,
+}
+
+impl RenderDocPlugin {
+    /// Start configuring a RenderDocPlugin.
+    pub fn builder() -> RenderDocPluginBuilder {
+        RenderDocPluginBuilder::default()
+    }
+}
+
+impl RenderDocPluginBuilder {
+    /// Trigger a capture when `key_code` is pressed, instead of the default F10.
+    pub fn trigger_key(self, key_code: KeyCode) -> Self {
+        self.trigger(key_code)
+    }
+
+    /// Trigger a capture when any [TriggerBinding] is pressed, instead of the default F10 key.
+    ///
+    /// This is the initial binding stored in [crate::RenderDocSettings], and can be changed at runtime.
+    pub fn trigger(mut self, binding: impl Into<TriggerBinding>) -> Self {
+        self.plugin.binding = Some(binding.into());
+        self
+    }
+
+    /// Reset the binding's key or button once it triggers a capture, so the rest of the app doesn't see it pressed.
+    ///
+    /// This is the initial value of [crate::RenderDocSettings::consume_trigger], and can be changed at runtime.
+    pub fn consume_trigger(mut self, consume: bool) -> Self {
+        self.plugin.consume_trigger = consume;
+        self
+    }
+
+    /// Ignore the binding while the primary window is unfocused.
+    ///
+    /// This is the initial value of [crate::RenderDocSettings::require_focus], and can be changed at runtime.
+    pub fn require_window_focus(mut self, require: bool) -> Self {
+        self.plugin.require_focus = require;
+        self
+    }
+
+    /// Start with the default hook disabled. [crate::RenderDocTrigger] must be used manually to trigger a capture,
+    /// unless a binding is later set in [crate::RenderDocSettings].
+    pub fn no_trigger(mut self) -> Self {
+        self.plugin.binding = None;
+        self
+    }
+
+    /// Set the path template captures are saved with, e.g. `captures/my_game` to save captures as
+    /// `captures/my_game_frame123.rdc`.
+    pub fn capture_path(mut self, template: impl Into<PathBuf>) -> Self {
+        self.plugin.capture_path = Some(template.into());
+        self
+    }
+
+    /// Set the initial [RenderDocOverlaySettings].
+    pub fn overlay(mut self, overlay: RenderDocOverlaySettings) -> Self {
+        self.plugin.overlay = Some(overlay);
+        self
+    }
+
+    /// Set the [CaptureOptions] applied to RenderDoc when the plugin is built.
+    pub fn options(mut self, options: CaptureOptions) -> Self {
+        self.plugin.capture_options = options;
+        self
+    }
+
+    /// Launch the RenderDoc replay UI with the capture loaded each time a capture is saved.
+    ///
+    /// The UI is not launched again while it is already connected to the application.
+    pub fn replay_ui_on_capture(mut self, open: bool) -> Self {
+        self.plugin.open_replay_ui = open;
+        self
+    }
+
+    /// Capture the first `frames` frames rendered by the app, starting with the very first one.
+    pub fn startup_capture_frames(mut self, frames: u32) -> Self {
+        self.plugin.startup_frames = frames;
+        self
+    }
+
+    /// Capture a frame every `interval` of real time, stopping after `max_captures` captures if set.
+    pub fn periodic_capture(mut self, interval: Duration, max_captures: Option<u32>) -> Self {
+        self.plugin.periodic_capture = Some((interval, max_captures));
+        self
+    }
+
+    /// Capture a frame when a frame takes longer than `threshold`, waiting at least `cooldown` between captures.
+    ///
+    /// A hitch is only known once its frame has finished, so the capture is of the frame following it.
+    pub fn spike_capture(mut self, threshold: Duration, cooldown: Duration) -> Self {
+        self.plugin.spike_capture = Some(SpikeCapture {
+            threshold,
+            cooldown,
+            last_capture: None,
+        });
+        self
+    }
+
+    /// Capture a frame when wgpu reports an uncaptured error, such as a validation error, attaching the error
+    /// message as a comment of that capture.
+    ///
+    /// This replaces wgpu's default error handler, which panics, with one that logs the error. Errors are only
+    /// reported once their frame has been submitted, so the capture is of the frame following the error. Errors
+    /// during the [trigger cooldown](Self::trigger_cooldown) after one are only logged.
+    pub fn validation_error_capture(mut self, enabled: bool) -> Self {
+        self.plugin.validation_error_capture = enabled;
+        self
+    }
+
+    /// Set whether render graph nodes and camera views are wrapped in debug groups named after their labels and
+    /// cameras while capturing, enabled by default.
+    ///
+    /// Nodes are wrapped once the render graph is complete, so [RenderGraph::get_node] can't downcast them to their
+    /// own type afterwards. Disable this if the app looks up its nodes after startup.
+    ///
+    /// [RenderGraph::get_node]: bevy::render::render_graph::RenderGraph::get_node
+    pub fn node_debug_groups(mut self, enabled: bool) -> Self {
+        self.plugin.node_debug_groups = enabled;
+        self
+    }
+
+    /// Label the GPU textures of images loaded from files with their asset path, for readable capture resource lists.
+    ///
+    /// Labels are set before images are uploaded, and only on images without one. Meshes aren't labelled, as Bevy
+    /// packs them into shared buffers, and neither are bind groups, which are labelled where they're created.
+    pub fn label_gpu_resources(mut self, enabled: bool) -> Self {
+        self.plugin.label_gpu_resources = enabled;
+        self
+    }
+
+    /// Render captured frames deterministically, so repeated captures of the same frame can be compared bit for bit,
+    /// e.g. for automated GPU regression tests.
+    ///
+    /// Captured frames are rendered with a time delta of `delta` and an elapsed time of `delta` per [FrameCount]
+    /// rather than the app's actual time, and temporally anti-aliased cameras aren't jittered. Only what the render
+    /// world sees is changed, so the main world's simulation can be made deterministic with
+    /// [TimeUpdateStrategy::ManualDuration] if needed.
+    ///
+    /// [FrameCount]: bevy::diagnostic::FrameCount
+    /// [TimeUpdateStrategy::ManualDuration]: bevy::time::TimeUpdateStrategy::ManualDuration
+    pub fn deterministic_captures(mut self, delta: Duration) -> Self {
+        self.plugin.deterministic_delta = Some(delta);
+        self
+    }
+
+    /// Panic when a capture fails, rather than only sending a [crate::CaptureFailed] event, e.g. so CI jobs taking
+    /// captures fail instead of silently producing none.
+    ///
+    /// Requesting a capture while RenderDoc isn't attached counts as a failure, so apps attaching RenderDoc later
+    /// should wait for [crate::renderdoc_available] before requesting captures.
+    pub fn strict(mut self, enabled: bool) -> Self {
+        self.plugin.strict = enabled;
+        self
+    }
+
+    /// Step through frames with `key`: pressing it captures the current frame and pauses [`Time<Virtual>`] once the
+    /// capture is saved, and each press while paused advances virtual time by one frame and captures that frame.
+    ///
+    /// Resume the app with [Time::unpause]. Only systems driven by virtual time are paused, as the app keeps
+    /// rendering, and fixed timestep systems run as many times as one frame's time delta accumulates to.
+    pub fn frame_step_key(mut self, key: KeyCode) -> Self {
+        self.plugin.frame_step_key = Some(key);
+        self
+    }
+
+    /// Compile shaders with debug info, so captured shaders can be debugged at source level.
+    ///
+    /// This slows down shader compilation and may disable some optimizations, so it's off by default. It sets
+    /// wgpu's debug instance flag, which `RenderPlugin` must be created with, see
+    /// [crate::RenderDocPlugin::wgpu_instance_flags].
+    pub fn shader_debug_info(mut self, enabled: bool) -> Self {
+        self.plugin.shader_debug_info = enabled;
+        self
+    }
+
+    /// Enable the graphics API's validation layers, whose messages capture tools show alongside the capture.
+    ///
+    /// Validation slows the app down considerably, so it's off by default. It sets wgpu's validation instance flag,
+    /// which `RenderPlugin` must be created with, see [crate::RenderDocPlugin::wgpu_instance_flags].
+    pub fn wgpu_validation(mut self, enabled: bool) -> Self {
+        self.plugin.wgpu_validation = enabled;
+        self
+    }
+
+    /// Set whether captures start out enabled, see [crate::RenderDocTrigger::set_enabled].
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.plugin.enabled = enabled;
+        self
+    }
+
+    /// Set how many capture requests can wait for a frame of their own before new ones are rejected with
+    /// [crate::CaptureError::QueueFull]. Defaults to 16.
+    pub fn max_queued_captures(mut self, max: usize) -> Self {
+        self.plugin.max_queued_captures = max;
+        self
+    }
+
+    /// Capture the frame `frames` frames after the one a capture was requested in, rather than that frame itself.
+    /// Applies to single-frame captures, not to bursts or spans. Defaults to 0.
+    ///
+    /// With pipelined rendering, requests are stamped with the main-world frame they were made in, so the offset is
+    /// counted from that frame regardless of which thread requested the capture.
+    pub fn capture_frame_offset(mut self, frames: u32) -> Self {
+        self.plugin.capture_frame_offset = frames;
+        self
+    }
+
+    /// Set the minimum interval between captures triggered by input or errors, i.e. the default hook's binding,
+    /// signals, the trigger file, touch gestures, leafwing-input-manager actions and validation errors, so a held or
+    /// bouncing key or a flood of errors doesn't take several captures in a row. Defaults to 500 ms, and
+    /// [Duration::ZERO] disables the cooldown.
+    ///
+    /// Suppressed captures send a [crate::CaptureSuppressed] event.
+    pub fn trigger_cooldown(mut self, cooldown: Duration) -> Self {
+        self.plugin.trigger_cooldown = cooldown;
+        self
+    }
+
+    /// If RenderDoc isn't attached when the plugin is built, keep trying to attach it every `interval` of real time.
+    ///
+    /// See [crate::RenderDocTrigger::try_attach] for attaching on demand instead.
+    pub fn attach_retry_interval(mut self, interval: Duration) -> Self {
+        self.plugin.attach_retry = Some(interval);
+        self
+    }
+
+    /// Load the RenderDoc library from `path` if RenderDoc isn't already loaded into the app, e.g. a copy bundled with
+    /// the game. The `RENDERDOC_LIB_PATH` environment variable takes precedence over this path.
+    pub fn library_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.plugin.library_path = Some(path.into());
+        self
+    }
+
+    /// Take captures with `backend` instead of RenderDoc, e.g. another graphics debugger. RenderDoc isn't loaded
+    /// at all then, and settings only RenderDoc supports are left to the backend to apply or ignore.
+    ///
+    /// The backend is used even where RenderDoc isn't supported or the `renderdoc` feature is disabled.
+    pub fn capture_backend(mut self, backend: impl FrameCaptureBackend) -> Self {
+        *self.plugin.capture_backend.get_mut().unwrap() = Some(Box::new(backend));
+        self
+    }
+
+    /// Capture the frame at which [FrameCount](bevy::diagnostic::FrameCount) equals `frame`, then print the path
+    /// of the saved capture to stdout and exit the app.
+    ///
+    /// The app exits with an error if the capture couldn't be taken, e.g. because RenderDoc isn't attached. The
+    /// `RENDERDOC_CAPTURE_FRAME` environment variable takes precedence over this frame.
+    pub fn capture_frame_and_exit(mut self, frame: u32) -> Self {
+        self.plugin.exit_after_capture = Some(frame);
+        self
+    }
+
+    /// Trigger a capture whenever the app receives `SIGUSR1`, e.g. from `kill -USR1 <pid>`.
+    ///
+    /// Only supported on Unix, and ignored with a warning elsewhere.
+    #[cfg(feature = "signal")]
+    pub fn signal_trigger(mut self, enabled: bool) -> Self {
+        self.plugin.signal_trigger = enabled;
+        self
+    }
+
+    /// Trigger a capture with a multi-finger tap gesture, e.g. on Android builds debugged through RenderDoc's remote
+    /// server without a keyboard attached. Captures triggered this way share the binding's
+    /// [trigger cooldown](Self::trigger_cooldown).
+    pub fn touch_trigger(mut self, gesture: TouchGesture) -> Self {
+        self.plugin.touch_gesture = Some(gesture);
+        self
+    }
+
+    /// Trigger a capture whenever a file is created at `path`, deleting it once the capture has been triggered.
+    ///
+    /// The file is checked for a few times a second, and its contents are ignored, e.g. `touch /tmp/bevy_capture`.
+    pub fn trigger_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.plugin.trigger_file = Some(path.into());
+        self
+    }
+
+    /// Record `build_info`, e.g. a commit hash or build ID, in the comments of each capture and append it to capture
+    /// file names, so captures can be matched to the binary that produced them. See [crate::build_info!].
+    pub fn build_info(mut self, build_info: impl Into<String>) -> Self {
+        self.plugin.build_info = Some(build_info.into());
+        self
+    }
+
+    /// Keep only the `count` most recent captures made with the capture path template, deleting older ones and the
+    /// files saved next to them whenever a capture is saved. At least the latest capture is always kept.
+    pub fn keep_recent_captures(mut self, count: usize) -> Self {
+        self.plugin.retention.max_count = Some(count);
+        self
+    }
+
+    /// Delete captures made with the capture path template once they are older than `age`, along with the files saved
+    /// next to them. Old captures are looked for whenever a capture is saved.
+    pub fn keep_captures_for(mut self, age: Duration) -> Self {
+        self.plugin.retention.max_age = Some(age);
+        self
+    }
+
+    /// Capture a frame whenever `condition` flips from `false` to `true`, e.g. [any_with_component] to capture the
+    /// frame an entity first appears in. The condition is evaluated in the main world each frame, in [PostUpdate].
+    ///
+    /// Can be called several times to add several conditions, each tracked on its own.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_renderdoc_capture::*;
+    /// #[derive(Component)]
+    /// struct Boss;
+    ///
+    /// # let mut app = App::new();
+    /// app.add_plugins(
+    ///     RenderDocPlugin::builder()
+    ///         .trigger_condition(any_with_component::<Boss>)
+    ///         .build(),
+    /// );
+    /// ```
+    pub fn trigger_condition<M>(mut self, condition: impl Condition<M>) -> Self {
+        let system = IntoSystem::into_system(condition.pipe(trigger_on_condition));
+        let add_system = move |app: &mut App| {
+            app.add_systems(PostUpdate, system.in_set(RenderDocSystems));
+        };
+        self.plugin
+            .trigger_conditions
+            .get_mut()
+            .unwrap()
+            .push(Box::new(add_system));
+        self
+    }
+
+    /// Capture a frame whenever `action` is just pressed, in an [ActionState] resource or component of the app's,
+    /// e.g. a `TakeGpuCapture` action in the app's debug action map.
+    ///
+    /// Checked in [PreUpdate] once leafwing-input-manager has updated action states. Captures are subject to the
+    /// [RenderDocPluginBuilder::trigger_cooldown] like those of the default hook. Can be called several times to add
+    /// several actions.
+    ///
+    /// [ActionState]: leafwing_input_manager::action_state::ActionState
+    #[cfg(feature = "leafwing")]
+    pub fn leafwing_action<A: leafwing_input_manager::Actionlike>(mut self, action: A) -> Self {
+        let add_system = move |app: &mut App| crate::leafwing::add_trigger_action(app, action);
+        self.plugin
+            .trigger_conditions
+            .get_mut()
+            .unwrap()
+            .push(Box::new(add_system));
+        self
+    }
+
+    /// Run `hook` for each capture as soon as it has been saved, e.g. to upload it or copy it to a network share.
+    ///
+    /// Hooks run on the main thread in [First], before [CaptureSaved] events are read, so slow work should be
+    /// moved to a task. Can be called several times to add several hooks.
+    pub fn on_capture_saved(
+        mut self,
+        hook: impl Fn(&CaptureSaved) + Send + Sync + 'static,
+    ) -> Self {
+        self.plugin.capture_saved_hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Compress each capture with gzip on a background task once it has been saved, sending a
+    /// [crate::CaptureCompressed] event once done. The uncompressed capture is deleted unless `keep_original` is set.
+    #[cfg(feature = "compress")]
+    pub fn compress_captures(mut self, keep_original: bool) -> Self {
+        self.plugin.compress_captures = Some(keep_original);
+        self
+    }
+
+    /// Keep RenderDoc's own hotkeys working alongside the plugin's binding: by default, F12 and Print Screen to capture
+    /// and F11 to switch the window it captures.
+    ///
+    /// They're disabled by default so every capture goes through the plugin, as captures taken through them aren't
+    /// reported with [CaptureSaved] events.
+    pub fn renderdoc_hotkeys(mut self, enabled: bool) -> Self {
+        self.plugin.renderdoc_hotkeys = enabled;
+        self
+    }
+
+    /// Set the keys switching which window RenderDoc considers active, replacing RenderDoc's default of F11. Applies
+    /// even if [RenderDocPluginBuilder::renderdoc_hotkeys] is disabled, and an empty list disables switching.
+    ///
+    /// The plugin's own captures target the window set with [crate::RenderDocTrigger::set_capture_window], or the
+    /// primary window, so the active window only matters for RenderDoc's overlay and for captures whose window can't
+    /// be found. Keys RenderDoc can't bind, such as modifiers or arrow keys, are skipped with a warning.
+    pub fn focus_toggle_keys(mut self, keys: impl IntoIterator<Item = KeyCode>) -> Self {
+        self.plugin.focus_toggle_keys = Some(keys.into_iter().collect());
+        self
+    }
+
+    /// Remove RenderDoc's crash handler once RenderDoc is attached, e.g. so it doesn't conflict with the app's own
+    /// crash reporter.
+    pub fn unload_crash_handler(mut self, unload: bool) -> Self {
+        self.plugin.unload_crash_handler = unload;
+        self
+    }
+
+    /// Read `capture`, `capture <frames>` and `status` commands from stdin, e.g. typed into the terminal the app was
+    /// launched from.
+    pub fn stdin_commands(mut self, enabled: bool) -> Self {
+        self.plugin.stdin_commands = enabled;
+        self
+    }
+
+    /// Listen for capture requests on `port` of the loopback interface, with `POST /capture` capturing the next frame
+    /// and `POST /capture?frames=N` capturing the next `N` frames.
+    #[cfg(feature = "remote")]
+    pub fn http_endpoint(mut self, port: u16) -> Self {
+        self.plugin.http_port = Some(port);
+        self
+    }
+
+    /// Finish configuring the plugin.
+    pub fn build(self) -> RenderDocPlugin {
+        self.plugin
+    }
+}
@@ -0,0 +1,95 @@
+//! Gzip compression of saved captures on a background task, enabled with the `compress` feature.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy::tasks::futures::check_ready;
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+use crate::CaptureSaved;
+
+/// Event sent once a capture has been compressed, see [crate::RenderDocPluginBuilder::compress_captures].
+#[derive(Event, Debug, Clone)]
+pub struct CaptureCompressed {
+    /// Path of the capture file that was compressed, which no longer exists unless the original was kept.
+    pub capture: PathBuf,
+    /// Path of the compressed capture, next to the capture file with an added `.gz` extension.
+    pub path: PathBuf,
+}
+
+/// Whether to keep captures after compressing them, and the compressions in progress.
+#[derive(Resource)]
+pub(crate) struct CaptureCompression {
+    keep_original: bool,
+    /// The capture each task compresses, along with the task.
+    tasks: Vec<(PathBuf, Task<io::Result<CaptureCompressed>>)>,
+}
+
+impl CaptureCompression {
+    pub(crate) fn new(keep_original: bool) -> Self {
+        Self {
+            keep_original,
+            tasks: Vec::new(),
+        }
+    }
+
+    /// Whether `capture` is still being compressed, so it mustn't be deleted yet.
+    pub(crate) fn is_compressing(&self, capture: &Path) -> bool {
+        self.tasks.iter().any(|(path, _)| path == capture)
+    }
+}
+
+/// Start compressing each saved capture, and send [CaptureCompressed] for each compression that finished.
+pub(crate) fn compress_saved_captures(
+    mut compression: ResMut<CaptureCompression>,
+    mut saved: EventReader<CaptureSaved>,
+    mut compressed: EventWriter<CaptureCompressed>,
+) {
+    let pool = AsyncComputeTaskPool::get();
+    for event in saved.read() {
+        let capture = event.path.clone();
+        let keep_original = compression.keep_original;
+        let task = pool.spawn({
+            let capture = capture.clone();
+            async move { compress(capture, keep_original) }
+        });
+        compression.tasks.push((capture, task));
+    }
+
+    compression.tasks.retain_mut(|(_, task)| {
+        let Some(result) = check_ready(task) else {
+            return true;
+        };
+
+        match result {
+            Ok(event) => {
+                info!("Compressed capture to {}", event.path.display());
+                compressed.write(event);
+            }
+            Err(e) => warn!("Failed to compress capture: {}", e),
+        }
+        false
+    });
+}
+
+/// Compress `capture` into a `.gz` file next to it.
+fn compress(capture: PathBuf, keep_original: bool) -> io::Result<CaptureCompressed> {
+    let mut path = capture.clone().into_os_string();
+    path.push(".gz");
+    let path = PathBuf::from(path);
+
+    let mut reader = BufReader::new(File::open(&capture)?);
+    let mut encoder = GzEncoder::new(BufWriter::new(File::create(&path)?), Compression::default());
+    io::copy(&mut reader, &mut encoder)?;
+    encoder.finish()?.into_inner().map_err(|e| e.into_error())?;
+
+    if !keep_original {
+        fs::remove_file(&capture)?;
+    }
+
+    Ok(CaptureCompressed { capture, path })
+}
@@ -0,0 +1,288 @@
+//! Settings loaded from a `renderdoc.toml` or `renderdoc.ron` file next to the executable, enabled with the `config`
+//! feature.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use ron::extensions::Extensions;
+use serde::Deserialize;
+
+use crate::env::parse_binding;
+use crate::{CaptureOptions, FileSettings};
+
+/// File names searched for next to the executable, in order.
+const FILE_NAMES: [&str; 2] = ["renderdoc.toml", "renderdoc.ron"];
+
+/// Settings read from the config file. Settings left out keep the values configured on the plugin.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ConfigFile {
+    /// Key binding in the same format as `BEVY_RENDERDOC_KEY`, e.g. `Ctrl+F12` or `none`.
+    trigger: Option<String>,
+    /// Path template captures are saved with.
+    capture_path: Option<PathBuf>,
+    /// Overlay parts to show or hide.
+    overlay: OverlayConfig,
+    /// Capture options, overriding those configured on the plugin one by one.
+    capture_options: CaptureOptions,
+}
+
+/// Overrides for each [crate::RenderDocOverlaySettings] field.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct OverlayConfig {
+    enabled: Option<bool>,
+    frame_rate: Option<bool>,
+    frame_number: Option<bool>,
+    capture_list: Option<bool>,
+}
+
+impl ConfigFile {
+    /// Load the first config file found next to the executable, warning if it can't be read or parsed.
+    pub(crate) fn load() -> Option<Self> {
+        let exe = std::env::current_exe().ok()?;
+        let dir = exe.parent()?;
+
+        let path = FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())?;
+
+        match Self::parse(&path) {
+            Ok(config) => {
+                info!("Loaded RenderDoc settings from {}", path.display());
+                Some(config)
+            }
+            Err(e) => {
+                warn!("Ignoring RenderDoc settings in {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    fn parse(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let ron = path.extension().is_some_and(|extension| extension == "ron");
+        Self::from_contents(&contents, ron)
+    }
+
+    /// Parse a config file's contents, as RON if `ron` is set or TOML otherwise.
+    fn from_contents(contents: &str, ron: bool) -> Result<Self, String> {
+        if ron {
+            ron::Options::default()
+                .with_default_extension(Extensions::IMPLICIT_SOME)
+                .from_str(contents)
+                .map_err(|e| e.to_string())
+        } else {
+            toml::from_str(contents).map_err(|e| e.to_string())
+        }
+    }
+
+    /// Override the settings configured on the plugin with those set in the file.
+    pub(crate) fn apply(self, mut settings: FileSettings) -> FileSettings {
+        if let Some(trigger) = self.trigger {
+            match parse_binding(&trigger) {
+                Some(trigger) => settings.binding = trigger,
+                None => warn!(
+                    "Ignoring RenderDoc trigger {:?}, as it isn't a key binding",
+                    trigger
+                ),
+            }
+        }
+
+        if self.capture_path.is_some() {
+            settings.capture_path = self.capture_path;
+        }
+
+        let mut overlay = settings.overlay.unwrap_or_default();
+        let fields = [
+            (&mut overlay.enabled, self.overlay.enabled),
+            (&mut overlay.frame_rate, self.overlay.frame_rate),
+            (&mut overlay.frame_number, self.overlay.frame_number),
+            (&mut overlay.capture_list, self.overlay.capture_list),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+        settings.overlay = Some(overlay);
+
+        let capture_options = &mut settings.capture_options;
+        let options = self.capture_options;
+        let fields = [
+            (&mut capture_options.api_validation, options.api_validation),
+            (
+                &mut capture_options.capture_callstacks,
+                options.capture_callstacks,
+            ),
+            (
+                &mut capture_options.capture_callstacks_only_draws,
+                options.capture_callstacks_only_draws,
+            ),
+            (
+                &mut capture_options.ref_all_resources,
+                options.ref_all_resources,
+            ),
+            (
+                &mut capture_options.verify_buffer_access,
+                options.verify_buffer_access,
+            ),
+            (&mut capture_options.allow_vsync, options.allow_vsync),
+            (
+                &mut capture_options.allow_fullscreen,
+                options.allow_fullscreen,
+            ),
+            (
+                &mut capture_options.debug_output_mute,
+                options.debug_output_mute,
+            ),
+            (
+                &mut capture_options.hook_into_children,
+                options.hook_into_children,
+            ),
+            (
+                &mut capture_options.capture_all_cmd_lists,
+                options.capture_all_cmd_lists,
+            ),
+        ];
+        for (field, value) in fields {
+            if value.is_some() {
+                *field = value;
+            }
+        }
+        if options.soft_memory_limit.is_some() {
+            capture_options.soft_memory_limit = options.soft_memory_limit;
+        }
+
+        settings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{KeyModifiers, RenderDocOverlaySettings, TriggerBinding};
+
+    use super::*;
+
+    /// Settings as configured on the plugin, before the file overrides them.
+    fn plugin_settings() -> FileSettings {
+        FileSettings {
+            binding: Some(TriggerBinding::Key(KeyCode::F10)),
+            capture_path: Some(PathBuf::from("plugin/capture")),
+            overlay: None,
+            capture_options: CaptureOptions {
+                allow_vsync: Some(false),
+                capture_callstacks: Some(true),
+                ..default()
+            },
+        }
+    }
+
+    #[test]
+    fn parses_toml() {
+        let config = ConfigFile::from_contents(
+            r#"
+            trigger = "Ctrl+F12"
+            capture_path = "captures/game"
+
+            [overlay]
+            frame_rate = false
+
+            [capture_options]
+            allow_vsync = true
+            soft_memory_limit = 512
+            "#,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(config.trigger.as_deref(), Some("Ctrl+F12"));
+        assert_eq!(config.capture_path, Some(PathBuf::from("captures/game")));
+        assert_eq!(config.overlay.frame_rate, Some(false));
+        assert_eq!(config.overlay.enabled, None);
+        assert_eq!(config.capture_options.allow_vsync, Some(true));
+        assert_eq!(config.capture_options.soft_memory_limit, Some(512));
+    }
+
+    #[test]
+    fn parses_ron() {
+        let config = ConfigFile::from_contents(
+            r#"(
+                trigger: "none",
+                overlay: (capture_list: false),
+                capture_options: (ref_all_resources: true),
+            )"#,
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(config.trigger.as_deref(), Some("none"));
+        assert_eq!(config.capture_path, None);
+        assert_eq!(config.overlay.capture_list, Some(false));
+        assert_eq!(config.capture_options.ref_all_resources, Some(true));
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        assert!(ConfigFile::from_contents("trigger_key = \"F12\"", false).is_err());
+        assert!(ConfigFile::from_contents("[overlay]\nfps = true", false).is_err());
+        assert!(ConfigFile::from_contents("(capture_options: (vsync: true))", true).is_err());
+    }
+
+    #[test]
+    fn overrides_only_the_settings_it_sets() {
+        let config = ConfigFile::from_contents(
+            r#"
+            trigger = "Shift+F11"
+
+            [overlay]
+            frame_number = false
+
+            [capture_options]
+            allow_vsync = true
+            "#,
+            false,
+        )
+        .unwrap();
+        let settings = config.apply(plugin_settings());
+
+        assert_eq!(
+            settings.binding,
+            Some(TriggerBinding::Chord {
+                modifiers: KeyModifiers {
+                    shift: true,
+                    ..default()
+                },
+                key: KeyCode::F11,
+            })
+        );
+        assert_eq!(settings.capture_path, Some(PathBuf::from("plugin/capture")));
+        assert_eq!(
+            settings.overlay,
+            Some(RenderDocOverlaySettings {
+                frame_number: false,
+                ..default()
+            })
+        );
+        assert_eq!(settings.capture_options.allow_vsync, Some(true));
+        assert_eq!(settings.capture_options.capture_callstacks, Some(true));
+        assert_eq!(settings.capture_options.api_validation, None);
+    }
+
+    #[test]
+    fn keeps_the_plugin_binding_for_invalid_triggers() {
+        let config = ConfigFile::from_contents("trigger = \"Ctrl++\"", false).unwrap();
+        let settings = config.apply(plugin_settings());
+
+        assert_eq!(settings.binding, Some(TriggerBinding::Key(KeyCode::F10)));
+    }
+
+    #[test]
+    fn disables_the_binding_with_none() {
+        let config = ConfigFile::from_contents("trigger = \"none\"", false).unwrap();
+
+        assert_eq!(config.apply(plugin_settings()).binding, None);
+    }
+}
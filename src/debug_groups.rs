@@ -0,0 +1,197 @@
+//! Debug groups around render graph nodes and the views they render, so captures show which node and camera
+//! recorded each pass and draw call.
+
+use bevy::prelude::*;
+use bevy::render::Extract;
+use bevy::render::camera::RenderTarget;
+use bevy::render::render_graph::{
+    EmptyNode, Node, NodeRunError, RenderGraph, RenderGraphContext, RenderLabel, SlotInfo,
+};
+use bevy::render::renderer::RenderContext;
+use bevy::render::sync_world::RenderEntity;
+use bevy::window::WindowRef;
+
+use crate::RenderDocData;
+
+/// Whether a capture is active, so debug groups are only recorded into captured frames.
+pub(crate) fn capturing(world: &World) -> bool {
+    world
+        .get_resource::<RenderDocData>()
+        .is_some_and(|renderdoc| renderdoc.is_capture_active)
+}
+
+/// A render graph node wrapped in a debug group named after its label while a capture is active.
+struct DebugGroupNode {
+    label: String,
+    node: Box<dyn Node>,
+}
+
+impl Node for DebugGroupNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        self.node.input()
+    }
+
+    fn output(&self) -> Vec<SlotInfo> {
+        self.node.output()
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.node.update(world);
+    }
+
+    fn run<'w>(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        // Only captures show the groups, so they're left out of every other frame.
+        if !capturing(world) {
+            return self.node.run(graph, render_context, world);
+        }
+
+        // Nodes recording their passes in parallel flush the encoder, so the group may end in a later command buffer
+        // than it started in, which capture tools nest correctly as they're submitted in order.
+        render_context
+            .command_encoder()
+            .push_debug_group(&self.label);
+        let result = self.node.run(graph, render_context, world);
+        render_context.command_encoder().pop_debug_group();
+        result
+    }
+}
+
+/// Label of the camera a view is rendered for, in the render world.
+#[derive(Component)]
+pub(crate) struct CameraDebugLabel(String);
+
+/// Label the render world's cameras after their [Name] and render target while capturing.
+pub(crate) fn extract_camera_labels(
+    mut commands: Commands,
+    renderdoc: Res<RenderDocData>,
+    cameras: Extract<Query<(&RenderEntity, &Camera, Option<&Name>)>>,
+) {
+    if !renderdoc.is_capture_active {
+        return;
+    }
+
+    for (render_entity, camera, name) in &cameras {
+        commands
+            .entity(render_entity.id())
+            .insert(CameraDebugLabel(camera_label(camera, name)));
+    }
+}
+
+/// Describe `camera` by its [Name] and render target, e.g. `Minimap (image minimap.png, 256x256)`.
+pub(crate) fn camera_label(camera: &Camera, name: Option<&Name>) -> String {
+    // Unnamed cameras are told apart by their order, as multi-camera setups give each camera a different one.
+    let name = name.map_or_else(
+        || format!("Camera {}", camera.order),
+        |name| name.to_string(),
+    );
+    let target = match &camera.target {
+        RenderTarget::Window(WindowRef::Primary) => "primary window".to_owned(),
+        RenderTarget::Window(WindowRef::Entity(window)) => format!("window {}", window),
+        RenderTarget::Image(image) => image
+            .handle
+            .path()
+            .map_or_else(|| "image".to_owned(), |path| format!("image {}", path)),
+        RenderTarget::TextureView(_) => "texture view".to_owned(),
+    };
+    match camera.physical_target_size() {
+        Some(size) => format!("{} ({}, {}x{})", name, target, size.x, size.y),
+        None => format!("{} ({})", name, target),
+    }
+}
+
+#[derive(RenderLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct BeginViewDebugGroup;
+
+#[derive(RenderLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct EndViewDebugGroup;
+
+/// Opens a debug group named after the camera a sub graph renders the view of, or the sub graph's label otherwise.
+struct BeginViewNode {
+    graph_label: String,
+}
+
+impl Node for BeginViewNode {
+    fn run<'w>(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        if !capturing(world) {
+            return Ok(());
+        }
+
+        let camera = graph
+            .get_view_entity()
+            .and_then(|view| world.get::<CameraDebugLabel>(view));
+        // Sub graphs run without a camera's view are only named after their label.
+        let label = match camera {
+            Some(CameraDebugLabel(label)) => label.as_str(),
+            None => self.graph_label.as_str(),
+        };
+        render_context.command_encoder().push_debug_group(label);
+        Ok(())
+    }
+}
+
+/// Closes the debug group opened by [BeginViewNode].
+struct EndViewNode;
+
+impl Node for EndViewNode {
+    fn run<'w>(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        if capturing(world) {
+            render_context.command_encoder().pop_debug_group();
+        }
+        Ok(())
+    }
+}
+
+/// Run every node of `graph` between a [BeginViewNode] and an [EndViewNode].
+fn add_view_group(graph: &mut RenderGraph, graph_label: String) {
+    let labels: Vec<_> = graph.iter_nodes().map(|state| state.label).collect();
+    if labels.is_empty() {
+        return;
+    }
+
+    graph.add_node(BeginViewDebugGroup, BeginViewNode { graph_label });
+    graph.add_node(EndViewDebugGroup, EndViewNode);
+    for label in labels {
+        graph.add_node_edge(BeginViewDebugGroup, label);
+        graph.add_node_edge(label, EndViewDebugGroup);
+    }
+}
+
+/// Wrap every node of `graph` and its sub graphs in a [DebugGroupNode], and each sub graph in a view group.
+fn wrap_nodes(graph: &mut RenderGraph) {
+    for state in graph.iter_nodes_mut() {
+        if state.node.downcast_ref::<DebugGroupNode>().is_some() {
+            continue;
+        }
+
+        let node = std::mem::replace(&mut state.node, Box::new(EmptyNode));
+        state.node = Box::new(DebugGroupNode {
+            label: format!("{:?}", state.label),
+            node,
+        });
+    }
+
+    for (label, sub_graph) in graph.iter_sub_graphs_mut() {
+        wrap_nodes(sub_graph);
+        add_view_group(sub_graph, format!("{:?}", label));
+    }
+}
+
+/// Wrap the render graph's nodes in debug groups, once every plugin has added its nodes.
+pub(crate) fn wrap_render_graph_nodes(mut graph: ResMut<RenderGraph>) {
+    wrap_nodes(&mut graph);
+}
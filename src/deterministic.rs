@@ -0,0 +1,48 @@
+//! Fixed time and camera jitter for captured frames, so repeated captures of the same frame render identically.
+
+use std::time::Duration;
+
+use bevy::diagnostic::FrameCount;
+use bevy::prelude::*;
+use bevy::render::camera::TemporalJitter;
+
+use crate::RenderDocData;
+
+/// The time delta captured frames are rendered with, see [crate::RenderDocPluginBuilder::deterministic_captures].
+#[derive(Resource)]
+pub(crate) struct DeterministicCapture {
+    pub(crate) delta: Duration,
+}
+
+/// Replace the render world's time while capturing with one advancing by the fixed delta every frame, so shaders
+/// see the same time whenever a frame is captured.
+pub(crate) fn fix_captured_time(
+    renderdoc: Res<RenderDocData>,
+    deterministic: Res<DeterministicCapture>,
+    frame_count: Res<FrameCount>,
+    mut time: ResMut<Time>,
+) {
+    if !renderdoc.is_capture_active {
+        return;
+    }
+
+    let delta = deterministic.delta;
+    let mut fixed = Time::default();
+    fixed.advance_to(delta * frame_count.0);
+    fixed.advance_by(delta);
+    *time = fixed;
+}
+
+/// Center the views of temporally anti-aliased cameras while capturing, rather than jittering them by frame.
+pub(crate) fn remove_captured_jitter(
+    renderdoc: Res<RenderDocData>,
+    mut jitters: Query<&mut TemporalJitter>,
+) {
+    if !renderdoc.is_capture_active {
+        return;
+    }
+
+    for mut jitter in &mut jitters {
+        jitter.offset = Vec2::ZERO;
+    }
+}
@@ -0,0 +1,63 @@
+//! Diagnostics for captures, shown alongside other diagnostics such as frame time.
+
+use std::time::Duration;
+
+use bevy::diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy::prelude::*;
+
+use crate::{CaptureHistory, CaptureSaved, RenderDocPlugin};
+
+impl RenderDocPlugin {
+    /// Number of captures saved this session.
+    pub const CAPTURES: DiagnosticPath = DiagnosticPath::const_new("renderdoc/captures");
+    /// Seconds since the last capture was saved.
+    pub const TIME_SINCE_CAPTURE: DiagnosticPath =
+        DiagnosticPath::const_new("renderdoc/time_since_capture");
+    /// Milliseconds the last capture was active for, including writing it to disk.
+    pub const CAPTURE_DURATION: DiagnosticPath =
+        DiagnosticPath::const_new("renderdoc/capture_duration");
+}
+
+/// Register the capture diagnostics. Like frame counts, averaging them would be meaningless.
+pub(crate) fn register_diagnostics(app: &mut App) {
+    for (path, suffix) in [
+        (RenderDocPlugin::CAPTURES, ""),
+        (RenderDocPlugin::TIME_SINCE_CAPTURE, "s"),
+        (RenderDocPlugin::CAPTURE_DURATION, "ms"),
+    ] {
+        app.register_diagnostic(
+            Diagnostic::new(path)
+                .with_suffix(suffix)
+                .with_smoothing_factor(0.0)
+                .with_max_history_length(0),
+        );
+    }
+}
+
+/// Update the capture diagnostics from the captures saved this frame.
+pub(crate) fn diagnostic_system(
+    mut diagnostics: Diagnostics,
+    mut events: EventReader<CaptureSaved>,
+    history: Res<CaptureHistory>,
+    time: Option<Res<Time<Real>>>,
+    mut last_capture: Local<Option<Duration>>,
+) {
+    let now = time.map(|time| time.elapsed());
+
+    if let Some(capture) = events.read().last() {
+        *last_capture = now;
+        diagnostics.add_measurement(&RenderDocPlugin::CAPTURE_DURATION, || {
+            capture.duration.as_secs_f64() * 1000.0
+        });
+    }
+
+    diagnostics.add_measurement(&RenderDocPlugin::CAPTURES, || {
+        history.captures().len() as f64
+    });
+
+    if let (Some(now), Some(last_capture)) = (now, *last_capture) {
+        diagnostics.add_measurement(&RenderDocPlugin::TIME_SINCE_CAPTURE, || {
+            (now - last_capture).as_secs_f64()
+        });
+    }
+}
@@ -0,0 +1,177 @@
+//! An egui debug panel for taking and managing captures, enabled with the `egui` feature.
+
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+
+use crate::{
+    CaptureHistory, CaptureOptions, RenderDocOverlaySettings, RenderDocStatus, RenderDocTrigger,
+};
+
+/// Adds an egui window for taking captures, toggling the overlay, changing capture options and opening saved
+/// captures in RenderDoc.
+///
+/// Requires [RenderDocPlugin](crate::RenderDocPlugin) and `bevy_egui`'s `EguiPlugin`, which is left for the app to
+/// add so it can choose the egui mode.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_egui::EguiPlugin;
+/// # use bevy_renderdoc_capture::*;
+/// # let mut app = App::new();
+/// app.add_plugins((
+///     RenderDocPlugin::default(),
+///     EguiPlugin::default(),
+///     RenderDocEguiPlugin,
+/// ));
+/// ```
+pub struct RenderDocEguiPlugin;
+
+impl Plugin for RenderDocEguiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(EguiPrimaryContextPass, renderdoc_panel);
+    }
+}
+
+/// Settings edited in the panel before being applied.
+struct PanelState {
+    frames: u32,
+    options: CaptureOptions,
+}
+
+impl Default for PanelState {
+    fn default() -> Self {
+        Self {
+            frames: 5,
+            options: CaptureOptions::default(),
+        }
+    }
+}
+
+/// Number of saved captures listed in the panel.
+const LISTED_CAPTURES: usize = 10;
+
+fn renderdoc_panel(
+    mut contexts: EguiContexts,
+    mut state: Local<PanelState>,
+    trigger: Res<RenderDocTrigger>,
+    status: Res<RenderDocStatus>,
+    history: Res<CaptureHistory>,
+    mut overlay: ResMut<RenderDocOverlaySettings>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    egui::Window::new("RenderDoc").show(ctx, |ui| {
+        if !status.available {
+            ui.label("RenderDoc is not attached.");
+            if ui.button("Try attaching").clicked() {
+                trigger.try_attach();
+            }
+            return;
+        }
+
+        if let Some(backend) = &status.backend {
+            ui.label(format!("Capturing with {}", backend));
+        }
+        if let Some((major, minor, patch)) = status.api_version {
+            ui.label(format!("API version {}.{}.{}", major, minor, patch));
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Capture").clicked() {
+                trigger.capture();
+            }
+            if ui.button("Capture and open").clicked() {
+                trigger.capture_and_open();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut state.frames).range(1..=100));
+            if ui.button("Capture frames").clicked() {
+                trigger.capture_frames(state.frames);
+            }
+        });
+
+        ui.collapsing("Overlay", |ui| {
+            let mut settings = overlay.clone();
+            ui.checkbox(&mut settings.enabled, "Enabled");
+            ui.checkbox(&mut settings.frame_rate, "Frame rate");
+            ui.checkbox(&mut settings.frame_number, "Frame number");
+            ui.checkbox(&mut settings.capture_list, "Capture list");
+            overlay.set_if_neq(settings);
+        });
+
+        ui.collapsing("Capture options", |ui| {
+            let options = &mut state.options;
+            option_toggle(ui, "API validation", &mut options.api_validation);
+            option_toggle(ui, "Callstacks", &mut options.capture_callstacks);
+            option_toggle(
+                ui,
+                "Callstacks for draws only",
+                &mut options.capture_callstacks_only_draws,
+            );
+            option_toggle(
+                ui,
+                "Reference all resources",
+                &mut options.ref_all_resources,
+            );
+            option_toggle(
+                ui,
+                "Verify buffer access",
+                &mut options.verify_buffer_access,
+            );
+            option_toggle(ui, "Allow vsync", &mut options.allow_vsync);
+            option_toggle(ui, "Allow fullscreen", &mut options.allow_fullscreen);
+            option_toggle(ui, "Mute debug output", &mut options.debug_output_mute);
+            option_toggle(ui, "Hook into children", &mut options.hook_into_children);
+            option_toggle(
+                ui,
+                "Capture all command lists",
+                &mut options.capture_all_cmd_lists,
+            );
+            ui.horizontal(|ui| {
+                let mut limited = options.soft_memory_limit.is_some();
+                ui.checkbox(&mut limited, "Soft memory limit (MB)");
+                let limit = options.soft_memory_limit.get_or_insert(0);
+                ui.add_enabled(limited, egui::DragValue::new(limit));
+                if !limited {
+                    options.soft_memory_limit = None;
+                }
+            });
+
+            if ui.button("Apply").clicked() {
+                trigger.set_capture_options(state.options.clone());
+            }
+        });
+
+        ui.collapsing("Saved captures", |ui| {
+            if history.captures().is_empty() {
+                ui.label("No captures yet.");
+            }
+
+            for capture in history.recent(LISTED_CAPTURES) {
+                ui.horizontal(|ui| {
+                    let name = capture.path.file_name().unwrap_or(capture.path.as_os_str());
+                    ui.label(name.to_string_lossy())
+                        .on_hover_text(capture.path.display().to_string());
+                    if ui.button("Open").clicked() {
+                        trigger.open_in_replay_ui(&capture.path);
+                    }
+                });
+            }
+        });
+    });
+
+    Ok(())
+}
+
+/// A three-way toggle for a capture option, where `None` keeps RenderDoc's own setting.
+fn option_toggle(ui: &mut egui::Ui, label: &str, value: &mut Option<bool>) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        ui.selectable_value(value, None, "Default");
+        ui.selectable_value(value, Some(true), "On");
+        ui.selectable_value(value, Some(false), "Off");
+    });
+}
@@ -0,0 +1,209 @@
+//! Settings read from environment variables when the plugin is built, so packaged builds can be reconfigured
+//! without a recompile.
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use bevy::reflect::{DynamicEnum, DynamicVariant, Typed, VariantInfo};
+
+use crate::{KeyModifiers, TriggerBinding};
+
+/// Key binding triggering a capture, e.g. `F12`, `Ctrl+Shift+F12` or `Ctrl+MouseBack`, or `none` to disable the
+/// default hook.
+const KEY_VAR: &str = "BEVY_RENDERDOC_KEY";
+/// Path template captures are saved with.
+const PATH_TEMPLATE_VAR: &str = "BEVY_RENDERDOC_PATH_TEMPLATE";
+/// Number of frames captured at startup.
+const FRAMES_VAR: &str = "BEVY_RENDERDOC_FRAMES";
+/// Disables captures when set to anything other than `0` or `false`.
+const DISABLE_VAR: &str = "BEVY_RENDERDOC_DISABLE";
+
+/// Plugin settings overridden through environment variables. Unset or invalid variables override nothing.
+#[derive(Debug)]
+pub(crate) struct EnvOverrides {
+    pub(crate) trigger: Option<Option<TriggerBinding>>,
+    pub(crate) capture_path: Option<PathBuf>,
+    pub(crate) startup_frames: Option<u32>,
+    pub(crate) disabled: bool,
+}
+
+impl EnvOverrides {
+    /// Read the overrides from the environment, warning about any that can't be parsed.
+    pub(crate) fn read() -> Self {
+        let trigger = var(KEY_VAR).and_then(|value| {
+            let binding = parse_binding(&value);
+            if binding.is_none() {
+                warn!(
+                    "Ignoring {}={:?}, as it isn't a key binding",
+                    KEY_VAR, value
+                );
+            }
+            binding
+        });
+
+        let startup_frames = var(FRAMES_VAR).and_then(|value| match value.parse() {
+            Ok(frames) => Some(frames),
+            Err(e) => {
+                warn!("Ignoring {}={:?}: {}", FRAMES_VAR, value, e);
+                None
+            }
+        });
+
+        let disabled = var(DISABLE_VAR)
+            .is_some_and(|value| !(value == "0" || value.eq_ignore_ascii_case("false")));
+
+        Self {
+            trigger,
+            capture_path: var(PATH_TEMPLATE_VAR).map(PathBuf::from),
+            startup_frames,
+            disabled,
+        }
+    }
+}
+
+/// The trimmed value of an environment variable, or `None` if it's unset or empty.
+fn var(name: &str) -> Option<String> {
+    let value = std::env::var(name).ok()?;
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_owned())
+}
+
+/// Parse a binding such as `F12`, `Ctrl+Shift+F12` or `MouseBack`, where `none` disables the binding.
+pub(crate) fn parse_binding(value: &str) -> Option<Option<TriggerBinding>> {
+    if value.eq_ignore_ascii_case("none") {
+        return Some(None);
+    }
+
+    let mut parts: Vec<_> = value.split('+').map(str::trim).collect();
+    let last = parts.pop()?;
+    let binding = match parse_mouse_button(last) {
+        Some(button) => TriggerBinding::Mouse(button),
+        None => TriggerBinding::Key(parse_key(last)?),
+    };
+    if parts.is_empty() {
+        return Some(Some(binding));
+    }
+
+    let mut modifiers = KeyModifiers::default();
+    for modifier in parts {
+        let held = match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => &mut modifiers.control,
+            "shift" => &mut modifiers.shift,
+            "alt" => &mut modifiers.alt,
+            "super" | "cmd" | "win" => &mut modifiers.super_key,
+            _ => return None,
+        };
+        *held = true;
+    }
+
+    let chord = match binding {
+        TriggerBinding::Key(key) => TriggerBinding::Chord { modifiers, key },
+        TriggerBinding::Mouse(button) => TriggerBinding::MouseChord { modifiers, button },
+        _ => return None,
+    };
+    Some(Some(chord))
+}
+
+/// Parse a [MouseButton] such as `MouseLeft` or `MouseBack`, where `Mouse4` and `Mouse5` are the back and forward
+/// side buttons.
+fn parse_mouse_button(name: &str) -> Option<MouseButton> {
+    let button = match name.to_ascii_lowercase().as_str() {
+        "mouseleft" => MouseButton::Left,
+        "mouseright" => MouseButton::Right,
+        "mousemiddle" => MouseButton::Middle,
+        "mouseback" | "mouse4" => MouseButton::Back,
+        "mouseforward" | "mouse5" => MouseButton::Forward,
+        _ => return None,
+    };
+    Some(button)
+}
+
+/// Parse a [KeyCode] from its variant name, e.g. `F12` or `KeyP`.
+fn parse_key(name: &str) -> Option<KeyCode> {
+    // Converting from an unknown variant panics, so check the variant exists first.
+    let variant = KeyCode::type_info().as_enum().ok()?.variant(name)?;
+    if !matches!(variant, VariantInfo::Unit(_)) {
+        return None;
+    }
+
+    KeyCode::from_reflect(&DynamicEnum::new(name, DynamicVariant::Unit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_keys() {
+        assert_eq!(
+            parse_binding("F12"),
+            Some(Some(TriggerBinding::Key(KeyCode::F12)))
+        );
+        assert_eq!(parse_key("KeyP"), Some(KeyCode::KeyP));
+    }
+
+    #[test]
+    fn parses_key_chords() {
+        let modifiers = KeyModifiers {
+            control: true,
+            shift: true,
+            ..default()
+        };
+        assert_eq!(
+            parse_binding("Ctrl+Shift+F12"),
+            Some(Some(TriggerBinding::Chord {
+                modifiers,
+                key: KeyCode::F12
+            }))
+        );
+        assert_eq!(
+            parse_binding(" ctrl + shift + F12 "),
+            parse_binding("Ctrl+Shift+F12")
+        );
+    }
+
+    #[test]
+    fn parses_mouse_bindings() {
+        assert_eq!(
+            parse_binding("Ctrl+MouseBack"),
+            Some(Some(TriggerBinding::MouseChord {
+                modifiers: KeyModifiers {
+                    control: true,
+                    ..default()
+                },
+                button: MouseButton::Back
+            }))
+        );
+        assert_eq!(
+            parse_binding("Mouse5"),
+            Some(Some(TriggerBinding::Mouse(MouseButton::Forward)))
+        );
+        assert_eq!(parse_mouse_button("MouseSide"), None);
+    }
+
+    #[test]
+    fn parses_none_as_no_binding() {
+        assert_eq!(parse_binding("none"), Some(None));
+        assert_eq!(parse_binding("NONE"), Some(None));
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert_eq!(parse_binding("F99"), None);
+        assert_eq!(parse_binding("Ctrl+Banana"), None);
+        // Variants with fields can't be named in a binding.
+        assert_eq!(parse_key("Unidentified"), None);
+    }
+
+    #[test]
+    fn rejects_unknown_modifiers() {
+        assert_eq!(parse_binding("Hyper+F12"), None);
+    }
+
+    #[test]
+    fn rejects_empty_segments() {
+        assert_eq!(parse_binding("Ctrl++F12"), None);
+        assert_eq!(parse_binding("Ctrl+"), None);
+        assert_eq!(parse_binding(""), None);
+    }
+}
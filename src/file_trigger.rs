@@ -0,0 +1,55 @@
+//! Triggering captures by creating a sentinel file, e.g. on remote machines where signals aren't available.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{CaptureSource, RenderDocTrigger};
+
+/// How often the sentinel file is checked for, in real time.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The sentinel file watched by [trigger_on_file].
+#[derive(Resource)]
+pub(crate) struct FileTrigger {
+    path: PathBuf,
+    timer: Timer,
+}
+
+impl FileTrigger {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            timer: Timer::new(POLL_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Trigger a capture if the sentinel file exists, deleting it so the next one can be requested.
+pub(crate) fn trigger_on_file(
+    mut file: ResMut<FileTrigger>,
+    time: Res<Time<Real>>,
+    trigger: Res<RenderDocTrigger>,
+) {
+    if !file.timer.tick(time.delta()).just_finished() || !file.path.exists() {
+        return;
+    }
+
+    // Only capture once per file, even if it can't be deleted.
+    if let Err(e) = fs::remove_file(&file.path) {
+        warn!(
+            "Failed to delete capture trigger file {}, no longer watching it: {}",
+            file.path.display(),
+            e
+        );
+        file.timer.pause();
+    }
+
+    info!(
+        "Found capture trigger file {}, triggering capture",
+        file.path.display()
+    );
+    trigger.capture_from(CaptureSource::File);
+}
@@ -0,0 +1,79 @@
+//! A debug marker at the start of each captured frame, naming the frame and its simulation time so frames of a
+//! multi-frame capture can be told apart and matched to the app's logs.
+
+use bevy::diagnostic::FrameCount;
+use bevy::prelude::*;
+use bevy::render::Extract;
+use bevy::render::render_graph::{
+    Node, NodeRunError, RenderGraph, RenderGraphContext, RenderLabel,
+};
+use bevy::render::renderer::RenderContext;
+
+use crate::RenderDocData;
+use crate::debug_groups::capturing;
+
+/// The marker text for the frame being rendered.
+#[derive(Resource)]
+pub(crate) struct FrameMarker(String);
+
+/// Describe the frame being extracted by its [FrameCount], virtual time and fixed timestep tick while capturing.
+pub(crate) fn extract_frame_marker(
+    mut commands: Commands,
+    renderdoc: Res<RenderDocData>,
+    frame_count: Extract<Option<Res<FrameCount>>>,
+    virtual_time: Extract<Option<Res<Time<Virtual>>>>,
+    fixed_time: Extract<Option<Res<Time<Fixed>>>>,
+) {
+    if !renderdoc.is_capture_active {
+        return;
+    }
+
+    let mut parts = Vec::new();
+    if let Some(frame_count) = frame_count.as_deref() {
+        parts.push(format!("frame {}", frame_count.0));
+    }
+    if let Some(time) = virtual_time.as_deref() {
+        parts.push(format!("virtual time {:.3}s", time.elapsed_secs_f64()));
+    }
+    if let Some(time) = fixed_time.as_deref() {
+        // Fixed time advances by whole timesteps, so it divides into the number of ticks run so far.
+        let tick = time.elapsed().as_nanos() / time.timestep().as_nanos().max(1);
+        parts.push(format!("fixed tick {}", tick));
+    }
+
+    commands.insert_resource(FrameMarker(parts.join(", ")));
+}
+
+#[derive(RenderLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct FrameMarkerLabel;
+
+/// Inserts the [FrameMarker] before the rest of the render graph runs.
+struct FrameMarkerNode;
+
+impl Node for FrameMarkerNode {
+    fn run<'w>(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        if !capturing(world) {
+            return Ok(());
+        }
+
+        if let Some(FrameMarker(marker)) = world.get_resource::<FrameMarker>() {
+            render_context.command_encoder().insert_debug_marker(marker);
+        }
+        Ok(())
+    }
+}
+
+/// Run a [FrameMarkerNode] before every other node of the render graph.
+pub(crate) fn add_frame_marker_node(mut graph: ResMut<RenderGraph>) {
+    let labels: Vec<_> = graph.iter_nodes().map(|state| state.label).collect();
+
+    graph.add_node(FrameMarkerLabel, FrameMarkerNode);
+    for label in labels {
+        graph.add_node_edge(FrameMarkerLabel, label);
+    }
+}
@@ -0,0 +1,68 @@
+//! Stepping a paused app one frame at a time, capturing each frame stepped to.
+
+use bevy::prelude::*;
+
+use crate::{CaptureSource, RenderDocTrigger};
+
+/// State of the key set with [crate::RenderDocPluginBuilder::frame_step_key].
+#[derive(Resource)]
+pub(crate) struct FrameStep {
+    key: KeyCode,
+    /// Whether the key was pressed while paused, so the next frame is stepped to.
+    requested: bool,
+    /// Whether time was unpaused for the frame being stepped to.
+    stepping: bool,
+}
+
+impl FrameStep {
+    pub(crate) fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            requested: false,
+            stepping: false,
+        }
+    }
+}
+
+/// Step a frame when the key is pressed while [`Time<Virtual>`] is paused, or capture the current frame and pause
+/// otherwise, to start stepping.
+pub(crate) fn read_frame_step_key(
+    mut step: ResMut<FrameStep>,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time<Virtual>>,
+    trigger: Res<RenderDocTrigger>,
+) {
+    if !keys.just_pressed(step.key) {
+        return;
+    }
+
+    if time.is_paused() {
+        step.requested = true;
+    } else {
+        trigger.capture_and_pause();
+    }
+}
+
+/// Unpause time before it's updated for the frame being stepped to.
+pub(crate) fn begin_frame_step(mut step: ResMut<FrameStep>, mut time: ResMut<Time<Virtual>>) {
+    if !std::mem::take(&mut step.requested) {
+        return;
+    }
+
+    time.unpause();
+    step.stepping = true;
+}
+
+/// Pause time again once it has advanced by one frame, and capture the frame.
+pub(crate) fn end_frame_step(
+    mut step: ResMut<FrameStep>,
+    mut time: ResMut<Time<Virtual>>,
+    trigger: Res<RenderDocTrigger>,
+) {
+    if !std::mem::take(&mut step.stepping) {
+        return;
+    }
+
+    time.pause();
+    trigger.capture_from(CaptureSource::FrameStep);
+}
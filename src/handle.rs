@@ -0,0 +1,129 @@
+//! Handles for awaiting the result of a requested capture.
+
+use std::fmt;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Reasons a requested capture did not produce a capture file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CaptureError {
+    /// RenderDoc is not attached to the application.
+    Unavailable,
+    /// Captures are disabled through [crate::RenderDocTrigger::set_enabled].
+    Disabled,
+    /// Too many captures were already queued, see [crate::RenderDocTrigger::queued_captures].
+    QueueFull,
+    /// The capture was cancelled with [crate::RenderDocTrigger::cancel].
+    Cancelled,
+    /// RenderDoc finished the capture without saving a capture file.
+    NotSaved,
+    /// The capture was triggered too soon after the previous one, see
+    /// [crate::RenderDocPluginBuilder::trigger_cooldown].
+    Cooldown,
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable => write!(f, "RenderDoc is not attached"),
+            Self::Disabled => write!(f, "RenderDoc captures are disabled"),
+            Self::QueueFull => write!(f, "the RenderDoc capture queue is full"),
+            Self::Cancelled => write!(f, "the capture was cancelled"),
+            Self::NotSaved => write!(f, "RenderDoc did not save a capture file"),
+            Self::Cooldown => write!(f, "the capture was triggered during the cooldown"),
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {}
+
+/// Result of a requested capture, resolving to the path of the saved capture file.
+pub type CaptureResult = Result<PathBuf, CaptureError>;
+
+/// Handle to a requested capture, returned by [crate::RenderDocTrigger::capture].
+///
+/// The handle can be polled each frame using [CaptureHandle::result], or awaited, e.g. on a task spawned with
+/// `bevy::tasks`. Dropping the handle does not cancel the capture.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy::tasks::IoTaskPool;
+/// # use bevy_renderdoc_capture::*;
+/// pub fn my_system(trigger: Res<RenderDocTrigger>) {
+///     let handle = trigger.capture();
+///
+///     IoTaskPool::get()
+///         .spawn(async move {
+///             match handle.await {
+///                 Ok(path) => info!("Capture saved to {}", path.display()),
+///                 Err(e) => warn!("Capture failed: {}", e),
+///             }
+///         })
+///         .detach();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CaptureHandle {
+    state: Arc<CaptureHandleState>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CaptureHandleState {
+    result: Mutex<Option<CaptureResult>>,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl CaptureHandle {
+    pub(crate) fn new() -> (Self, Arc<CaptureHandleState>) {
+        let state = Arc::new(CaptureHandleState::default());
+        (
+            Self {
+                state: state.clone(),
+            },
+            state,
+        )
+    }
+
+    /// The result of the capture, or `None` if it has not finished yet.
+    pub fn result(&self) -> Option<CaptureResult> {
+        self.state.result.lock().unwrap().clone()
+    }
+
+    /// Whether the capture has finished, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        self.state.result.lock().unwrap().is_some()
+    }
+}
+
+impl CaptureHandleState {
+    /// Resolve the handle, waking any task awaiting it.
+    pub(crate) fn resolve(&self, result: CaptureResult) {
+        *self.result.lock().unwrap() = Some(result);
+
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Future for CaptureHandle {
+    type Output = CaptureResult;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Hold the waker lock while checking, so a concurrent `resolve` can't slip in between.
+        let mut waker = self.state.waker.lock().unwrap();
+
+        match self.result() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                *waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! A localhost HTTP endpoint for triggering captures from external scripts, enabled with the `remote` feature.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{CaptureRequests, RenderDocTrigger};
+
+/// How long a client has to send its request before the connection is dropped.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Listen for capture requests on `port` of the loopback interface, warning if the port can't be bound.
+pub(crate) fn spawn(requests: Arc<CaptureRequests>, port: u16) {
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!(
+                "Failed to listen for capture requests on port {}: {}",
+                port, e
+            );
+            return;
+        }
+    };
+
+    info!(
+        "Listening for capture requests on http://127.0.0.1:{}",
+        port
+    );
+
+    // Accepting connections blocks, so the endpoint gets its own thread rather than tying up a task pool thread.
+    let trigger = RenderDocTrigger { requests };
+    let spawned = std::thread::Builder::new()
+        .name("renderdoc-http".into())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let result = stream.and_then(|stream| handle_connection(&trigger, stream));
+                if let Err(e) = result {
+                    debug!("Failed to handle capture request: {}", e);
+                }
+            }
+        });
+
+    if let Err(e) = spawned {
+        warn!("Failed to spawn the capture request thread: {}", e);
+    }
+}
+
+/// Read a single request from `stream` and write its response.
+fn handle_connection(trigger: &RenderDocTrigger, stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Requests never need a body, so the headers are skipped without being parsed.
+    let mut header = String::new();
+    loop {
+        header.clear();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => respond(trigger, method, target),
+        _ => ("400 Bad Request", "malformed request".to_owned()),
+    };
+
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// Handle a request for `target`, returning the response status and body.
+fn respond(trigger: &RenderDocTrigger, method: &str, target: &str) -> (&'static str, String) {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    if path != "/capture" {
+        return (
+            "404 Not Found",
+            "unknown path, use POST /capture".to_owned(),
+        );
+    }
+    if method != "POST" {
+        return ("405 Method Not Allowed", "use POST /capture".to_owned());
+    }
+
+    let mut frames = None;
+    for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+        match (key, value.parse()) {
+            ("frames", Ok(0)) => {
+                return ("400 Bad Request", "frames must be at least 1".to_owned());
+            }
+            ("frames", Ok(value)) => frames = Some(value),
+            _ => return ("400 Bad Request", format!("invalid parameter {}", key)),
+        }
+    }
+
+    if let Some(error) = trigger.rejection() {
+        return ("503 Service Unavailable", error.to_string());
+    }
+
+    let queued = trigger.queued_captures();
+    match frames {
+        Some(frames) => trigger.capture_frames(frames),
+        None => {
+            trigger.capture();
+        }
+    }
+
+    (
+        "202 Accepted",
+        format!("capture queued behind {} others", queued),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+
+    /// A trigger accepting captures, as if a backend was attached.
+    fn available_trigger() -> RenderDocTrigger {
+        let requests = Arc::new(CaptureRequests {
+            max_queued: crate::DEFAULT_MAX_QUEUED_CAPTURES,
+            ..Default::default()
+        });
+        requests.available.store(true, Ordering::SeqCst);
+        requests.enabled.store(true, Ordering::SeqCst);
+        RenderDocTrigger { requests }
+    }
+
+    #[test]
+    fn queues_a_capture() {
+        let trigger = available_trigger();
+
+        let (status, body) = respond(&trigger, "POST", "/capture");
+        assert_eq!(status, "202 Accepted");
+        assert_eq!(body, "capture queued behind 0 others");
+        assert_eq!(trigger.queued_captures(), 1);
+
+        let (_, body) = respond(&trigger, "POST", "/capture");
+        assert_eq!(body, "capture queued behind 1 others");
+    }
+
+    #[test]
+    fn captures_several_frames() {
+        let trigger = available_trigger();
+
+        let (status, _) = respond(&trigger, "POST", "/capture?frames=3");
+        assert_eq!(status, "202 Accepted");
+        assert_eq!(trigger.requests.frames.load(Ordering::SeqCst), 3);
+        assert_eq!(trigger.queued_captures(), 0);
+    }
+
+    #[test]
+    fn rejects_invalid_frames() {
+        let trigger = available_trigger();
+
+        for target in [
+            "/capture?frames=0",
+            "/capture?frames=many",
+            "/capture?title=x",
+        ] {
+            let (status, _) = respond(&trigger, "POST", target);
+            assert_eq!(status, "400 Bad Request", "{}", target);
+        }
+        assert_eq!(trigger.queued_captures(), 0);
+        assert_eq!(trigger.requests.frames.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn rejects_other_paths_and_methods() {
+        let trigger = available_trigger();
+
+        assert_eq!(respond(&trigger, "POST", "/").0, "404 Not Found");
+        assert_eq!(
+            respond(&trigger, "GET", "/capture").0,
+            "405 Method Not Allowed"
+        );
+        assert_eq!(trigger.queued_captures(), 0);
+    }
+
+    #[test]
+    fn reports_unavailable_captures() {
+        let trigger = available_trigger();
+        trigger.requests.available.store(false, Ordering::SeqCst);
+
+        assert_eq!(
+            respond(&trigger, "POST", "/capture").0,
+            "503 Service Unavailable"
+        );
+
+        trigger.requests.available.store(true, Ordering::SeqCst);
+        trigger.set_enabled(false);
+        assert_eq!(
+            respond(&trigger, "POST", "/capture").0,
+            "503 Service Unavailable"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Conversion of Bevy key codes to the keys RenderDoc's hotkeys can be bound to.
+
+use bevy::input::keyboard::KeyCode;
+use renderdoc::InputButton;
+
+/// The RenderDoc key matching `key`, or `None` if RenderDoc can't bind it.
+pub(crate) fn input_button(key: KeyCode) -> Option<InputButton> {
+    let button = match key {
+        KeyCode::Digit0 => InputButton::Key0,
+        KeyCode::Digit1 => InputButton::Key1,
+        KeyCode::Digit2 => InputButton::Key2,
+        KeyCode::Digit3 => InputButton::Key3,
+        KeyCode::Digit4 => InputButton::Key4,
+        KeyCode::Digit5 => InputButton::Key5,
+        KeyCode::Digit6 => InputButton::Key6,
+        KeyCode::Digit7 => InputButton::Key7,
+        KeyCode::Digit8 => InputButton::Key8,
+        KeyCode::Digit9 => InputButton::Key9,
+        KeyCode::KeyA => InputButton::A,
+        KeyCode::KeyB => InputButton::B,
+        KeyCode::KeyC => InputButton::C,
+        KeyCode::KeyD => InputButton::D,
+        KeyCode::KeyE => InputButton::E,
+        KeyCode::KeyF => InputButton::F,
+        KeyCode::KeyG => InputButton::G,
+        KeyCode::KeyH => InputButton::H,
+        KeyCode::KeyI => InputButton::I,
+        KeyCode::KeyJ => InputButton::J,
+        KeyCode::KeyK => InputButton::K,
+        KeyCode::KeyL => InputButton::L,
+        KeyCode::KeyM => InputButton::M,
+        KeyCode::KeyN => InputButton::N,
+        KeyCode::KeyO => InputButton::O,
+        KeyCode::KeyP => InputButton::P,
+        KeyCode::KeyQ => InputButton::Q,
+        KeyCode::KeyR => InputButton::R,
+        KeyCode::KeyS => InputButton::S,
+        KeyCode::KeyT => InputButton::T,
+        KeyCode::KeyU => InputButton::U,
+        KeyCode::KeyV => InputButton::V,
+        KeyCode::KeyW => InputButton::W,
+        KeyCode::KeyX => InputButton::X,
+        KeyCode::KeyY => InputButton::Y,
+        KeyCode::KeyZ => InputButton::Z,
+        KeyCode::F1 => InputButton::F1,
+        KeyCode::F2 => InputButton::F2,
+        KeyCode::F3 => InputButton::F3,
+        KeyCode::F4 => InputButton::F4,
+        KeyCode::F5 => InputButton::F5,
+        KeyCode::F6 => InputButton::F6,
+        KeyCode::F7 => InputButton::F7,
+        KeyCode::F8 => InputButton::F8,
+        KeyCode::F9 => InputButton::F9,
+        KeyCode::F10 => InputButton::F10,
+        KeyCode::F11 => InputButton::F11,
+        KeyCode::F12 => InputButton::F12,
+        KeyCode::NumpadDivide => InputButton::Divide,
+        KeyCode::NumpadMultiply => InputButton::Multiply,
+        KeyCode::NumpadSubtract => InputButton::Subtract,
+        KeyCode::NumpadAdd => InputButton::Plus,
+        KeyCode::Home => InputButton::Home,
+        KeyCode::End => InputButton::End,
+        KeyCode::Insert => InputButton::Insert,
+        KeyCode::Delete => InputButton::Delete,
+        KeyCode::PageUp => InputButton::PageUp,
+        KeyCode::PageDown => InputButton::PageDn,
+        KeyCode::Backspace => InputButton::Backspace,
+        KeyCode::Tab => InputButton::Tab,
+        KeyCode::PrintScreen => InputButton::PrtScrn,
+        KeyCode::Pause => InputButton::Pause,
+        _ => return None,
+    };
+
+    Some(button)
+}
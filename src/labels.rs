@@ -0,0 +1,35 @@
+//! Debug labels for GPU textures derived from their image's asset path, so captures name them after their source
+//! files.
+
+use bevy::asset::AssetPath;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::render::render_asset::ExtractedAssets;
+use bevy::render::texture::GpuImage;
+
+/// Labels given to textures so far, leaked once per path as wgpu texture descriptors only take static labels.
+#[derive(Resource, Default)]
+pub(crate) struct ImageLabels(HashMap<AssetPath<'static>, &'static str>);
+
+/// Label the images extracted this frame after their asset path before they're uploaded, unless already labelled.
+pub(crate) fn label_extracted_images(
+    mut extracted: ResMut<ExtractedAssets<GpuImage>>,
+    asset_server: Res<AssetServer>,
+    mut labels: ResMut<ImageLabels>,
+) {
+    for (id, image) in &mut extracted.extracted {
+        if image.texture_descriptor.label.is_some() {
+            continue;
+        }
+        // Images created at runtime have no path to be named after.
+        let Some(path) = asset_server.get_path(*id) else {
+            continue;
+        };
+
+        let label = *labels
+            .0
+            .entry(path.into_owned())
+            .or_insert_with_key(|path| Box::leak(path.to_string().into_boxed_str()));
+        image.texture_descriptor.label = Some(label);
+    }
+}
@@ -0,0 +1,42 @@
+//! Triggering captures with a leafwing-input-manager action, enabled with the `leafwing` feature.
+
+use bevy::prelude::*;
+use leafwing_input_manager::Actionlike;
+use leafwing_input_manager::action_state::ActionState;
+use leafwing_input_manager::plugin::InputManagerSystem;
+
+use crate::{CaptureSource, RenderDocSystems, RenderDocTrigger};
+
+/// The actions of type `A` triggering a capture, see [crate::RenderDocPluginBuilder::leafwing_action].
+#[derive(Resource)]
+struct TriggerActions<A: Actionlike>(Vec<A>);
+
+/// Capture whenever `action` is just pressed, adding the system checking actions of its type the first time.
+pub(crate) fn add_trigger_action<A: Actionlike>(app: &mut App, action: A) {
+    if let Some(mut actions) = app.world_mut().get_resource_mut::<TriggerActions<A>>() {
+        actions.0.push(action);
+        return;
+    }
+
+    app.insert_resource(TriggerActions(vec![action]));
+    app.add_systems(
+        PreUpdate,
+        trigger_on_action::<A>
+            .after(InputManagerSystem::ManualControl)
+            .in_set(RenderDocSystems),
+    );
+}
+
+/// Trigger a capture when one of the actions is just pressed, in the [ActionState] resource or any entity's
+/// [ActionState] component.
+fn trigger_on_action<A: Actionlike>(
+    actions: Res<TriggerActions<A>>,
+    resource: Option<Res<ActionState<A>>>,
+    entities: Query<&ActionState<A>>,
+    trigger: Res<RenderDocTrigger>,
+) {
+    let mut states = resource.as_deref().into_iter().chain(&entities);
+    if states.any(|state| actions.0.iter().any(|action| state.just_pressed(action))) {
+        trigger.capture_from(CaptureSource::Action);
+    }
+}
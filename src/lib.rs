@@ -2,6 +2,8 @@
 //! # Examples
 //! ## No configuration
 //! With no configuration, a default hook is added that allows you to press `F10` to trigger a frame capture.
+//! RenderDoc's own capture hotkeys are disabled, so every capture goes through the plugin, unless re-enabled with
+//! [RenderDocPluginBuilder::renderdoc_hotkeys].
 //!
 //! ```no_run
 //! # use bevy::prelude::*;
@@ -11,178 +13,3940 @@
 //! ```
 //!
 //! ## With selected key code
-//! Alternatively, you can configure which key the hook uses:
+//! Alternatively, you can configure which key the hook uses, along with any other settings, through
+//! [RenderDocPlugin::builder]:
 //!
 //! ```no_run
 //! # use bevy::prelude::*;
 //! # use bevy_renderdoc_capture::*;
 //! # let mut app = App::new();
-//! app.add_plugins(RenderDocPlugin::new_with_trigger_key(KeyCode::F12));
+//! app.add_plugins(RenderDocPlugin::builder().trigger_key(KeyCode::F12).build());
 //! ```
 //!
-//! ## Without default hook
-//! Or you can bring your own hook using [RenderDocTrigger].
+//! ## Capturing several frames
+//! [RenderDocTrigger::capture_frames] captures a number of consecutive frames, each saved as its own capture:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! pub fn my_system(trigger: Res<RenderDocTrigger>) {
+//!     trigger.capture_frames(5);
+//! }
+//! ```
+//!
+//! [RenderDocTrigger::capture_burst] spreads the captures out instead, e.g. capturing every 10th frame 6 times.
+//!
+//! ## Capturing a span of frames
+//! [RenderDocTrigger::start_capture] and [RenderDocTrigger::end_capture] record a single capture spanning every frame
+//! in between, e.g. from the start of a level load until the first frame is rendered:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! pub fn on_level_load(trigger: Res<RenderDocTrigger>) {
+//!     trigger.start_capture();
+//! }
+//!
+//! pub fn on_level_ready(trigger: Res<RenderDocTrigger>) {
+//!     trigger.end_capture();
+//! }
+//! ```
+//!
+//! ## Reacting to saved captures
+//! A [CaptureSaved] event is sent once RenderDoc has written a capture to disk:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! pub fn log_captures(mut events: EventReader<CaptureSaved>) {
+//!     for event in events.read() {
+//!         info!("Capture {} saved to {}", event.index, event.path.display());
+//!     }
+//! }
+//! ```
+//!
+//! ## Opening captures in RenderDoc
+//! The replay UI can be launched as soon as a capture is saved, either for every capture or per request using
+//! [RenderDocTrigger::capture_and_open]:
 //!
 //! ```no_run
 //! # use bevy::prelude::*;
 //! # use bevy_renderdoc_capture::*;
 //! # let mut app = App::new();
-//! app.add_plugins(RenderDocPlugin::new_without_trigger());
-//! app.add_systems(Update, my_system);
+//! app.add_plugins(RenderDocPlugin::builder().replay_ui_on_capture(true).build());
+//! ```
 //!
-//! // ...
+//! ## Configuring the overlay
+//! RenderDoc's in-application overlay is controlled through the [RenderDocOverlaySettings] resource, and is updated
+//! whenever the resource changes:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! pub fn hide_overlay(mut overlay: ResMut<RenderDocOverlaySettings>) {
+//!     overlay.enabled = false;
+//! }
+//! ```
+//!
+//! ## Capture options
+//! RenderDoc's capture options can be set through [CaptureOptions]. These are applied when the plugin is built, so
+//! add the plugin before `DefaultPlugins` for options affecting device creation, such as API validation:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(
+//!     RenderDocPlugin::builder()
+//!         .options(CaptureOptions {
+//!             api_validation: Some(true),
+//!             ref_all_resources: Some(true),
+//!             ..default()
+//!         })
+//!         .build(),
+//! );
+//! ```
+//!
+//! ## Capture failures
+//! A [CaptureFailed] event is sent when a capture fails, e.g. because it was requested while RenderDoc isn't attached
+//! or RenderDoc didn't save it. With [RenderDocPluginBuilder::strict], the app panics instead, so CI jobs relying on
+//! captures fail loudly:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().strict(true).build());
+//! ```
+//!
+//! ## Checking whether RenderDoc is attached
+//! The [RenderDocStatus] resource describes the attached RenderDoc instance, and the [renderdoc_available] run
+//! condition can gate debug-only systems on it:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! # fn debug_ui() {}
+//! app.add_systems(Update, debug_ui.run_if(renderdoc_available));
+//! ```
+//!
+//! Similarly, [RenderDocTrigger::is_capturing] and the [in_renderdoc_capture] run condition tell whether the render
+//! world has a capture in progress, e.g. to draw extra debug gizmos into captured frames only.
 //!
+//! ## Naming captures
+//! [RenderDocTrigger::capture_named] gives the capture a title, so it can be told apart in the RenderDoc UI:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
 //! pub fn my_system(trigger: Res<RenderDocTrigger>) {
+//!     trigger.capture_named("shadow-artifact-repro");
+//! }
+//! ```
 //!
-//!     // Do some checks...
+//! ## Triggering captures from commands
+//! A plugin-registered observer captures whenever [RenderDocCapture] is triggered, so captures can be requested
+//! wherever [Commands] are available, e.g. in UI callbacks:
 //!
-//!     trigger.capture();
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! pub fn my_system(mut commands: Commands) {
+//!     commands.trigger(RenderDocCapture { frames: 1, name: None });
 //! }
 //! ```
 //!
-#![deny(missing_docs, reason = "Document your public APIs!!!")]
+//! Adding [CaptureOnSpawn] to an entity captures the frame it's spawned in, or a number of frames later.
+//!
+//! Render code can't reach [RenderDocTrigger], so custom render graph nodes and render-world systems arm captures
+//! with the [RenderDocCaptureRequests] resource instead, e.g. to capture the frame after a readback finds a NaN.
+//!
+//! ## With a gamepad button
+//! The hook can also be bound to a gamepad button, or to any other [TriggerBinding]:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().trigger(GamepadButton::Select).build());
+//! ```
+//!
+//! ## With a key chord
+//! To avoid accidental captures, the hook can require modifier keys to be held:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(
+//!     RenderDocPlugin::builder()
+//!         .trigger(TriggerBinding::Chord {
+//!             modifiers: KeyModifiers {
+//!                 control: true,
+//!                 shift: true,
+//!                 ..default()
+//!             },
+//!             key: KeyCode::F10,
+//!         })
+//!         .build(),
+//! );
+//! ```
+//!
+//! ## With a leafwing-input-manager action
+//! With the `leafwing` feature, [RenderDocPluginBuilder::leafwing_action] captures whenever an action of the app's
+//! own action map is pressed, so the capture binding can sit alongside the app's other debug actions, with whatever
+//! chords and gamepad buttons its input map binds to it. The default hook can be disabled with
+//! [RenderDocPluginBuilder::no_trigger] in that case.
+//!
+//! ## Capturing a clicked entity
+//! With the `picking` feature, Ctrl+clicking an entity captures the next frame, recording the entity, its name and
+//! the asset handles its components hold, such as its mesh and material, in the capture's comments. This relies on
+//! the app's picking backend, e.g. `MeshPickingPlugin`, and only handles registered for reflection are listed.
+//!
+//! ## With a mouse button
+//! Without easy access to function keys, e.g. on tablet-style setups, a mouse button or a
+//! [TriggerBinding::MouseChord] can trigger captures instead:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().trigger(MouseButton::Back).build());
+//! ```
+//!
+//! ## Scheduling captures
+//! Captures can be armed for a later frame, by delay or by [FrameCount](bevy::diagnostic::FrameCount):
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! pub fn my_system(trigger: Res<RenderDocTrigger>) {
+//!     trigger.capture_in(30);
+//!     trigger.capture_at_frame(600);
+//! }
+//! ```
+//!
+//! ## Pipelined rendering
+//! With `PipelinedRenderingPlugin`, the render world renders a frame while the main world already runs the next one.
+//! Each capture request is stamped with the main-world frame it was made in, whichever thread made it, and captures
+//! the rendering of that frame once it has been extracted. What's on screen at that moment is an earlier frame, which
+//! has already been rendered and can't be captured anymore.
+//!
+//! To capture a later frame instead, e.g. once the effects of whatever triggered the capture have been rendered,
+//! delay captures by a number of frames:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().capture_frame_offset(1).build());
+//! ```
+//!
+//! ## Capturing startup
+//! The first frames rendered can be captured to inspect initialization, such as initial resource uploads:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().startup_capture_frames(1).build());
+//! ```
+//!
+//! ## Periodic captures
+//! For soak tests, a capture can be taken at a fixed interval, optionally up to a maximum number of captures:
+//!
+//! ```no_run
+//! # use std::time::Duration;
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(
+//!     RenderDocPlugin::builder()
+//!         .periodic_capture(Duration::from_secs(600), Some(100))
+//!         .build(),
+//! );
+//! ```
+//!
+//! ## Capturing hitches
+//! A capture can be triggered automatically when a frame takes longer than a threshold, with a cooldown so a
+//! single hitch doesn't produce a flood of captures:
+//!
+//! ```no_run
+//! # use std::time::Duration;
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(
+//!     RenderDocPlugin::builder()
+//!         .spike_capture(Duration::from_millis(50), Duration::from_secs(10))
+//!         .build(),
+//! );
+//! ```
+//!
+//! ## Capturing validation errors
+//! wgpu validation errors can trigger a capture, with the error message attached as a capture comment. This
+//! replaces wgpu's default handler, so errors are logged instead of panicking:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().validation_error_capture(true).build());
+//! ```
+//!
+//! ## Changing captured frames
+//! Systems in the [DuringCapture] set run only on frames that are about to be captured, and systems in the
+//! [AfterCapture] set run once afterwards, e.g. to show debug meshes in captures only:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! # fn show_debug_meshes() {}
+//! # fn hide_debug_meshes() {}
+//! app.add_systems(Last, show_debug_meshes.in_set(DuringCapture))
+//!     .add_systems(Last, hide_debug_meshes.in_set(AfterCapture));
+//! ```
+//!
+//! ## Render graph debug groups
+//! While a capture is active, each render graph node runs inside a debug group named after its label, so the
+//! capture's event browser groups passes and draw calls under the node that recorded them, e.g. `ShadowPass` or
+//! `MainOpaquePass`. Each camera's view is grouped too, named after the camera's [Name] and render target, e.g.
+//! `Minimap (image minimap.png, 256x256)`, so multi-camera scenes stay readable. This can be turned off with
+//! [RenderDocPluginBuilder::node_debug_groups].
+//!
+//! Cameras with [CaptureThisView] have their views wrapped in a `Captured view: ` group regardless, and are listed in
+//! the capture's comments, so the passes of a render-to-texture camera are easy to find. Its `skip_other_views`
+//! option goes further, deactivating every other camera on captured frames:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! pub fn spawn_minimap_camera(mut commands: Commands, minimap: Handle<Image>) {
+//!     commands.spawn((
+//!         Camera {
+//!             target: minimap.into(),
+//!             ..default()
+//!         },
+//!         CaptureThisView {
+//!             skip_other_views: true,
+//!         },
+//!     ));
+//! }
+//! ```
+//!
+//! With or without debug groups, each captured frame starts with a marker naming its [FrameCount], virtual time and
+//! fixed timestep tick, e.g. `frame 1234, virtual time 20.567s, fixed tick 1316`, so the frames of a multi-frame
+//! capture can be told apart and matched to the app's logs.
+//!
+//! ## Deterministic captures
+//! With [RenderDocPluginBuilder::deterministic_captures], captured frames are rendered with a fixed time delta and
+//! without temporal anti-aliasing jitter, so capturing the same frame twice gives identical captures:
+//!
+//! ```no_run
+//! # use std::time::Duration;
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(
+//!     RenderDocPlugin::builder()
+//!         .deterministic_captures(Duration::from_secs_f64(1.0 / 60.0))
+//!         .build(),
+//! );
+//! ```
+//!
+//! ## Labelled textures
+//! With [RenderDocPluginBuilder::label_gpu_resources], textures of images loaded from files are labelled with their
+//! asset path, so the capture's resource list shows `textures/brick_albedo.png` rather than `Texture 213`:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().label_gpu_resources(true).build());
+//! ```
+//!
+//! ## Cancelling captures
+//! [RenderDocTrigger::cancel] drops pending captures and discards the one in progress, e.g. after an accidental key
+//! press:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! pub fn cancel_on_escape(trigger: Res<RenderDocTrigger>, keys: Res<ButtonInput<KeyCode>>) {
+//!     if keys.just_pressed(KeyCode::Escape) {
+//!         trigger.cancel();
+//!     }
+//! }
+//! ```
+//!
+//! Once a debugging session is over, [RenderDocTrigger::detach] stops the plugin for the rest of the process, so a
+//! long-running editor no longer pays for it.
+//!
+//! ## Choosing the captured window
+//! In apps with several windows, [RenderDocTrigger::capture_window] captures a specific window, while
+//! [RenderDocTrigger::set_capture_window] changes the window used for all captures:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! #[derive(Resource)]
+//! pub struct ViewportWindow(Entity);
+//!
+//! pub fn capture_viewport(trigger: Res<RenderDocTrigger>, viewport: Res<ViewportWindow>) {
+//!     trigger.capture_window(viewport.0);
+//! }
+//! ```
+//!
+//! ## Disabling captures at runtime
+//! Captures can be switched off with [RenderDocTrigger::set_enabled], e.g. to ship internal builds with the plugin
+//! inert until enabled from a debug menu:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().enabled(false).build());
+//! app.add_systems(Update, toggle_captures);
+//!
+//! pub fn toggle_captures(trigger: Res<RenderDocTrigger>, keys: Res<ButtonInput<KeyCode>>) {
+//!     if keys.just_pressed(KeyCode::F11) {
+//!         trigger.set_enabled(!trigger.is_enabled());
+//!     }
+//! }
+//! ```
+//!
+//! ## Attaching RenderDoc later
+//! If the app wasn't launched from RenderDoc, attaching can be retried periodically, or on demand with
+//! [RenderDocTrigger::try_attach], e.g. after injecting RenderDoc into the running process:
+//!
+//! ```no_run
+//! # use std::time::Duration;
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(
+//!     RenderDocPlugin::builder()
+//!         .attach_retry_interval(Duration::from_secs(5))
+//!         .build(),
+//! );
+//! ```
+//!
+//! ## Custom library path
+//! If RenderDoc isn't already loaded into the app, e.g. because it wasn't launched from RenderDoc, the plugin can load
+//! the RenderDoc library itself from a given path. The `RENDERDOC_LIB_PATH` environment variable overrides the path
+//! set on the plugin. The library must be loaded before the render device is created, so add the plugin before
+//! `DefaultPlugins`:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(
+//!     RenderDocPlugin::builder()
+//!         .library_path("tools/renderdoc/librenderdoc.so")
+//!         .build(),
+//! );
+//! app.add_plugins(DefaultPlugins);
+//! ```
+//!
+//! ## wgpu debug flags
+//! [RenderDocPluginBuilder::shader_debug_info] has wgpu compile shaders with debug info, so captured shaders can be
+//! stepped through in their WGSL source, and [RenderDocPluginBuilder::wgpu_validation] enables the graphics API's
+//! validation layers, whose messages capture tools show alongside the capture. Both slow the app down, so they're off
+//! by default. wgpu reads them from `WgpuSettings::instance_flags` when `RenderPlugin` creates the renderer, so the
+//! app adds [RenderDocPlugin::wgpu_instance_flags] to the settings it creates `RenderPlugin` with:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy::render::RenderPlugin;
+//! # use bevy::render::settings::WgpuSettings;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! let renderdoc = RenderDocPlugin::builder().shader_debug_info(true).build();
+//! let mut settings = WgpuSettings::default();
+//! settings.instance_flags |= renderdoc.wgpu_instance_flags();
+//! app.add_plugins(DefaultPlugins.set(RenderPlugin {
+//!     render_creation: settings.into(),
+//!     ..default()
+//! }));
+//! app.add_plugins(renderdoc);
+//! ```
+//!
+//! The plugin warns if `RenderPlugin` was created without the flags it asks for.
+//!
+//! ## Calling the RenderDoc API directly
+//! While RenderDoc is attached, the render world holds a `RenderDocApi` resource whose `with_api` method calls a
+//! closure with the RenderDoc API, for entry points the plugin doesn't wrap yet. The plugin keeps starting and ending
+//! captures itself, so the API's own capture functions are best left alone.
+//!
+//! ## Other capture tools
+//! Captures are taken through a [FrameCaptureBackend], RenderDoc by default. Implementing it for another tool keeps
+//! the plugin's triggers, queueing and events while the backend only starts and ends captures:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! struct MyDebugger;
+//!
+//! impl FrameCaptureBackend for MyDebugger {
+//!     fn start_frame_capture(&mut self, target: &CaptureTarget, title: Option<&str>) {
+//!         // Start capturing on `target.device`.
+//!     }
+//!
+//!     fn end_frame_capture(&mut self, target: &CaptureTarget) -> Option<CaptureFile> {
+//!         // Finish the capture and report where it was saved.
+//!         None
+//!     }
+//!
+//!     fn status(&self) -> RenderDocStatus {
+//!         RenderDocStatus {
+//!             available: true,
+//!             ..default()
+//!         }
+//!     }
+//! }
+//!
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().capture_backend(MyDebugger).build());
+//! ```
+//!
+//! Without one set, the plugin probes the backends compiled in when it's built, and takes captures with the first
+//! available: PIX, RenderDoc, then Metal. The chosen backend is named in [RenderDocStatus::backend] and sent once in a
+//! [CaptureBackendSelected] event. If the app then renders with a graphics backend the chosen one can't capture, e.g.
+//! GL with PIX, a warning suggesting an alternative is logged and sent as [UnsupportedGraphicsBackend].
+//!
+//! ## Rebinding the trigger
+//! The default hook's binding lives in the [RenderDocSettings] resource, so it can be changed at runtime, e.g. from
+//! an in-game keybinding menu:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! pub fn rebind_capture(mut settings: ResMut<RenderDocSettings>) {
+//!     settings.trigger = Some(TriggerBinding::Key(KeyCode::F9));
+//! }
+//! ```
+//!
+//! If the binding's key also means something to the game, e.g. toggling its HUD, [RenderDocSettings::consume_trigger]
+//! resets it once it triggers a capture, so the rest of the app doesn't see it pressed.
+//!
+//! It also holds RenderDoc's vsync and fullscreen options, which are re-applied whenever they change, since forcing
+//! an app windowed or without vsync can hide presentation bugs:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! pub fn allow_present_modes(mut settings: ResMut<RenderDocSettings>) {
+//!     settings.allow_fullscreen = Some(true);
+//!     settings.allow_vsync = Some(true);
+//! }
+//! ```
+//!
+//! ## Desktop notifications
+//! With the `notify` feature, a desktop notification is shown whenever a capture is saved, so captures taken from a
+//! fullscreen app don't go unnoticed.
+//!
+//! ## In-game toasts
+//! With the `toast` feature, a small toast naming the saved capture is shown in the top right corner for a few
+//! seconds. As it's drawn by the app, it shows up in captures taken while it's visible.
+//!
+//! ## Archiving captures
+//! Callbacks added with [RenderDocPluginBuilder::on_capture_saved] run as soon as each capture is written, e.g. to copy
+//! it to a network share. They run on the main thread, so slow work is best moved to a task:
+//!
+//! ```no_run
+//! # use std::path::Path;
+//! # use bevy::prelude::*;
+//! # use bevy::tasks::IoTaskPool;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(
+//!     RenderDocPlugin::builder()
+//!         .on_capture_saved(|capture| {
+//!             let path = capture.path.clone();
+//!             IoTaskPool::get()
+//!                 .spawn(async move {
+//!                     let archived = Path::new("/mnt/share/captures").join(path.file_name().unwrap());
+//!                     if let Err(e) = std::fs::copy(&path, archived) {
+//!                         warn!("Failed to archive {}: {}", path.display(), e);
+//!                     }
+//!                 })
+//!                 .detach();
+//!         })
+//!         .build(),
+//! );
+//! ```
+//!
+//! ## Deleting old captures
+//! Captures can take up gigabytes each, so long sessions can be limited to keeping the most recent captures, or those
+//! taken within a given time. Older captures made with the same path template are deleted, along with any files saved
+//! next to them, whenever a new capture is saved:
+//!
+//! ```no_run
+//! # use std::time::Duration;
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(
+//!     RenderDocPlugin::builder()
+//!         .keep_recent_captures(20)
+//!         .keep_captures_for(Duration::from_secs(24 * 60 * 60))
+//!         .build(),
+//! );
+//! ```
+//!
+//! ## Compressing captures
+//! With the `compress` feature, `RenderDocPluginBuilder::compress_captures` gzips each capture on a background task
+//! once it's saved, e.g. before sending it to a colleague, optionally deleting the uncompressed capture. A
+//! `CaptureCompressed` event is sent with the path of each compressed capture.
+//!
+//! ## Screenshots
+//! With the `screenshot` feature, a screenshot of the captured window is saved next to each capture, as a PNG with the
+//! same name, so a folder of captures can be browsed without opening each one. As captures are only known once
+//! saved, the screenshot is of a frame shortly after the captured one. Headless captures get no screenshot.
+//!
+//! ## Pausing after a capture
+//! [RenderDocTrigger::capture_and_pause] pauses [`Time<Virtual>`] once the capture has been saved, so the scene stays
+//! frozen as captured while the capture is inspected side by side. With the `state` feature,
+//! `RenderDocAppExt::add_renderdoc_pause_state` enters a state instead, e.g. a debug pause menu.
+//!
+//! ## Stepping frames
+//! With [RenderDocPluginBuilder::frame_step_key], pressing the key captures the current frame and pauses the app as
+//! [RenderDocTrigger::capture_and_pause] does. While paused, each press advances the app by exactly one frame and
+//! captures it, e.g. to follow an animation or skinning glitch frame by frame:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().frame_step_key(KeyCode::F10).build());
+//! ```
+//!
+//! ## Capturing state transitions
+//! With the `state` feature, `RenderDocAppExt::add_renderdoc_capture_on_enter` captures the first frame after
+//! entering a state, so the start of a problematic game state can be captured reproducibly, optionally a number of
+//! frames later with `add_renderdoc_capture_on_enter_after`.
+//!
+//! ## Sidecar metadata files
+//! With the `sidecar` feature, a JSON file with the same name is written next to each capture, recording the frame
+//! number, timings and camera transforms, e.g. for a bug tracker to fill in repro details. App-specific metadata, such
+//! as the current `States` through `StateMetadata`, is added through `CaptureMetadataSources`.
+//!
+//! ## Scene snapshots
+//! With the `scene` feature, a snapshot of the main world is saved next to each capture as a `.scn.ron` scene, so the
+//! CPU-side state of the captured frame can be reloaded to reproduce it. What's included is chosen with
+//! `CaptureSceneFilter`, and only reflected types registered with the app are saved.
+//!
+//! ## Debug panel
+//! With the `egui` feature, adding `RenderDocEguiPlugin` shows an egui window for taking captures, toggling the
+//! overlay, changing capture options and opening saved captures, without writing any code.
+//!
+//! ## Remote control
+//! With the `brp` feature and Bevy's `RemotePlugin` added, external tools can take captures through the
+//! `bevy_renderdoc/capture` method, which accepts optional `frames` and `title` parameters, and query
+//! [RenderDocStatus] and [CaptureHistory] through the `bevy_renderdoc/status` method.
+//!
+//! ## Diagnostics
+//! The number of captures, time since the last capture and how long it was active for are recorded as diagnostics,
+//! e.g. logged by `LogDiagnosticsPlugin` under [RenderDocPlugin::CAPTURES], [RenderDocPlugin::TIME_SINCE_CAPTURE]
+//! and [RenderDocPlugin::CAPTURE_DURATION].
+//!
+//! ## Capture metadata
+//! Each capture's comments record the Bevy and wgpu versions, the graphics adapter, the captured frame's number and
+//! the number of entities, so captures can still be triaged long after they were taken. A comment of your own can be
+//! added with [RenderDocTrigger::set_comment]. Capture comments require RenderDoc 1.2 or newer.
+//!
+//! ## Build information
+//! A build identifier, e.g. a commit hash, can be recorded in the capture comments and appended to capture file names,
+//! so captures can be matched to the binary that produced them. The [build_info!] macro reads one set by the app's
+//! build script, falling back to the app's version:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().build_info(build_info!()).build());
+//! ```
+//!
+//! With a `build.rs` setting `BEVY_RENDERDOC_BUILD_INFO` such as:
+//!
+//! ```no_run
+//! let hash = std::process::Command::new("git")
+//!     .args(["rev-parse", "--short", "HEAD"])
+//!     .output()
+//!     .ok()
+//!     .and_then(|output| String::from_utf8(output.stdout).ok());
+//!
+//! if let Some(hash) = hash {
+//!     println!("cargo:rustc-env=BEVY_RENDERDOC_BUILD_INFO={}", hash.trim());
+//! }
+//! ```
+//!
+//! ## Headless apps
+//! Apps rendering off-screen without a window, e.g. render tests using `ScheduleRunnerPlugin`, can be captured too.
+//! Without a window to match, captures record everything rendered on the render device, and [CaptureSaved::window]
+//! is `None`. As there is no keyboard input either, captures are taken by code or at startup:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(
+//!     RenderDocPlugin::builder()
+//!         .no_trigger()
+//!         .startup_capture_frames(1)
+//!         .build(),
+//! );
+//! ```
+//!
+//! ## Capturing a frame and exiting
+//! For automated GPU regression captures, e.g. on a headless CI machine, the plugin can capture the frame at which
+//! [FrameCount] reaches a given number, print the path of the saved capture to stdout and exit the app. Exiting
+//! uses [AppExit::Success] once the capture is saved, or an error if it couldn't be taken. The
+//! `RENDERDOC_CAPTURE_FRAME` environment variable enables this without a code change, and overrides the frame set on
+//! the plugin:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().capture_frame_and_exit(120).build());
+//! ```
+//!
+//! ## Triggering captures with a signal
+//! With the `signal` feature, `RenderDocPluginBuilder::signal_trigger` triggers a capture on Unix whenever the app
+//! receives `SIGUSR1`, e.g. from `kill -USR1 <pid>` over SSH on a test machine without a keyboard attached.
+//!
+//! ## Triggering captures with a file
+//! Where signals aren't available, e.g. on devkits or remote Windows machines, the plugin can watch for a sentinel
+//! file instead. Creating the file triggers a capture, and the plugin deletes it again so the next capture can be
+//! triggered the same way:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().trigger_file("/tmp/bevy_capture").build());
+//! ```
+//!
+//! ## Triggering captures with a touch gesture
+//! On mobile devices without a keyboard, e.g. Android builds debugged through RenderDoc's remote server, a
+//! multi-finger tap gesture can trigger captures:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(
+//!     RenderDocPlugin::builder()
+//!         .touch_trigger(TouchGesture::default())
+//!         .build(),
+//! );
+//! ```
+//!
+//! ## Android
+//! On Android, the plugin attaches to the RenderDoc layer injected by RenderDoc's remote server when launching the
+//! app. Captures in progress when the app is suspended are cancelled, resolving their [CaptureHandle] to
+//! [CaptureError::Cancelled], and captures keep working once the app resumes with a new window.
+//!
+//! ## macOS and iOS
+//! RenderDoc doesn't support Metal, so on Apple platforms the plugin builds without RenderDoc, like on the web. The
+//! `metal` feature adds `MetalCaptureBackend`, which takes the same captures through Metal's capture manager instead,
+//! saving them as `.gputrace` documents that open in Xcode. It's picked automatically when the app is run from Xcode
+//! or with `METAL_CAPTURE_ENABLED=1` set, which Metal needs to allow captures at all.
+//!
+//! ## PIX
+//! On Windows, the `pix` feature takes captures with PIX instead of RenderDoc whenever PIX is attached to the app,
+//! through WinPixEventRuntime's programmatic capture API, so the same triggers work whichever tool launched the app.
+//! `WinPixEventRuntime.dll` must be next to the executable, and the app must render with DX12. Captures are saved as
+//! `.wpix` files named after the capture path template.
+//!
+//! ## Web
+//! RenderDoc can't capture browsers' WebGPU or WebGL, so on `wasm32` targets the plugin builds without RenderDoc and
+//! only logs that it's unsupported. Its resources are still added, with [RenderDocStatus::available] left `false`
+//! and every capture request rejected, so apps targeting both the web and desktop can keep a single plugin list.
+//!
+//! ## Release builds
+//! RenderDoc is loaded through the default `renderdoc` feature. Without it, the plugin compiles to the same stub as on
+//! the web, without depending on RenderDoc at all, so release builds can keep the plugin in code at no runtime cost.
+//! One way to do so is having the app's own default feature enable it:
+//!
+//! ```toml
+//! [dependencies]
+//! bevy-renderdoc-capture = { version = "0.2", default-features = false }
+//!
+//! [features]
+//! default = ["renderdoc"]
+//! renderdoc = ["bevy-renderdoc-capture/renderdoc"]
+//! ```
+//!
+//! Release builds are then made with `cargo build --release --no-default-features`.
+//!
+//! ## Profiling
+//! The `trace` feature enables Bevy's tracing spans and adds `renderdoc_capture_start` and `renderdoc_capture_end`
+//! spans around starting and ending captures, along with a `RenderDoc capture frame 1234` message, so hitches seen in
+//! tracy or a chrome trace can be matched to the captures taken during the same session.
+//!
+//! ## Commands from the terminal
+//! When the app is launched from a terminal, e.g. under `renderdoccmd`, the plugin can read commands from stdin so
+//! captures can be triggered without focusing the app's window. `capture` captures the next frame, `capture 5` the
+//! next 5 frames, and `status` prints whether RenderDoc is attached along with the latest capture:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().stdin_commands(true).build());
+//! ```
+//!
+//! ## HTTP endpoint
+//! With the `remote` feature, `RenderDocPluginBuilder::http_endpoint` makes the plugin listen on a localhost port,
+//! so external scripts, editors and test harnesses can trigger captures with `POST /capture`, or
+//! `POST /capture?frames=3` for several frames, e.g. `curl -X POST http://127.0.0.1:8787/capture`.
+//!
+//! ## Config file
+//! With the `config` feature, settings are also loaded from a `renderdoc.toml` or `renderdoc.ron` file next to the
+//! executable, if there is one, so per-machine setups don't need code changes. Settings in the file take precedence
+//! over those configured on the plugin, and environment variables take precedence over both. Every setting is
+//! optional:
+//!
+//! ```toml
+//! trigger = "Ctrl+F12"
+//! capture_path = "captures/my_game"
+//!
+//! [overlay]
+//! frame_rate = false
+//!
+//! [capture_options]
+//! capture_callstacks = true
+//! ```
+//!
+//! ## Environment variables
+//! Some settings can be overridden when the app starts, e.g. by QA on a packaged build, taking precedence over the
+//! settings configured on the plugin:
+//!
+//! - `BEVY_RENDERDOC_KEY`: the key binding, e.g. `F12` or `Ctrl+Shift+F12`, or `none` to disable the default hook.
+//!   Keys are named after their [KeyCode] variant.
+//! - `BEVY_RENDERDOC_PATH_TEMPLATE`: the path template captures are saved with.
+//! - `BEVY_RENDERDOC_FRAMES`: the number of frames captured at startup.
+//! - `BEVY_RENDERDOC_DISABLE`: disables captures unless set to `0` or `false`. They can still be enabled at runtime
+//!   with [RenderDocTrigger::set_enabled].
+//!
+//! ## Without default hook
+//! Or you can bring your own hook using [RenderDocTrigger].
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # let mut app = App::new();
+//! app.add_plugins(RenderDocPlugin::builder().no_trigger().build());
+//! app.add_systems(Update, my_system);
+//!
+//! // ...
+//!
+//! pub fn my_system(trigger: Res<RenderDocTrigger>) {
+//!
+//!     // Do some checks...
+//!
+//!     trigger.capture();
+//! }
+//! ```
+//!
+//! ## Testing triggers
+//! Custom hooks can be tested in headless apps without RenderDoc, using [test::RenderDocTestPlugin] in place of
+//! [RenderDocPlugin] and asserting on the frames captures were requested for with [test::assert_capture_requested].
+//! [MockCaptureBackend] takes the place of RenderDoc in apps that do render.
+//!
+#![deny(missing_docs, reason = "Document your public APIs!!!")]
+
+#[cfg_attr(
+    any(
+        target_arch = "wasm32",
+        target_vendor = "apple",
+        not(feature = "renderdoc")
+    ),
+    path = "api_stub.rs"
+)]
+mod api;
+mod backend;
+mod builder;
+#[cfg(feature = "compress")]
+mod compress;
+#[cfg(feature = "config")]
+mod config;
+mod debug_groups;
+mod deterministic;
+mod diagnostics;
+#[cfg(feature = "egui")]
+mod egui_panel;
+mod env;
+mod file_trigger;
+mod frame_marker;
+mod frame_step;
+mod handle;
+#[cfg(feature = "remote")]
+mod http;
+#[cfg(all(
+    not(any(target_arch = "wasm32", target_vendor = "apple")),
+    feature = "renderdoc"
+))]
+mod keys;
+mod labels;
+#[cfg(feature = "leafwing")]
+mod leafwing;
+mod metadata;
+#[cfg(all(feature = "metal", target_vendor = "apple"))]
+mod metal_capture;
+mod mock;
+#[cfg(feature = "notify")]
+mod notify;
+#[cfg(feature = "picking")]
+mod picking;
+#[cfg(all(feature = "pix", windows))]
+mod pix;
+#[cfg(feature = "brp")]
+mod remote;
+mod retention;
+#[cfg(feature = "scene")]
+mod scene;
+#[cfg(feature = "screenshot")]
+mod screenshot;
+#[cfg(feature = "sidecar")]
+mod sidecar;
+#[cfg(all(unix, feature = "signal"))]
+mod signal;
+#[cfg(feature = "state")]
+mod state;
+mod stdin;
+mod target;
+pub mod test;
+#[cfg(feature = "toast")]
+mod toast;
+mod touch;
+mod view_scope;
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use bevy::diagnostic::FrameCount;
+use bevy::ecs::entity::Entities;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::render::extract_resource::{ExtractResource, extract_resource};
+use bevy::render::render_asset::{ExtractedAssets, prepare_assets};
+use bevy::render::renderer::{RenderAdapterInfo, RenderDevice};
+use bevy::render::settings::{Backends, InstanceFlags, RenderCreation};
+use bevy::render::texture::GpuImage;
+use bevy::render::view::ExtractedWindows;
+use bevy::render::{Extract, ExtractSchedule, Render, RenderApp, RenderPlugin, RenderSet};
+use bevy::time::TimeSystem;
+use bevy::window::{AppLifecycle, PrimaryWindow};
+use wgpu::Backend;
+
+use crate::backend::RenderDocBackend;
+pub use crate::backend::{CaptureFile, CaptureTarget, FrameCaptureBackend};
+pub use crate::builder::RenderDocPluginBuilder;
+#[cfg(feature = "compress")]
+pub use crate::compress::CaptureCompressed;
+#[cfg(feature = "egui")]
+pub use crate::egui_panel::RenderDocEguiPlugin;
+use crate::handle::CaptureHandleState;
+pub use crate::handle::{CaptureError, CaptureHandle, CaptureResult};
+use crate::metadata::CaptureMetadata;
+#[cfg(all(feature = "metal", target_vendor = "apple"))]
+pub use crate::metal_capture::MetalCaptureBackend;
+pub use crate::mock::{MockCapture, MockCaptureBackend, MockCaptureLog};
+#[cfg(feature = "scene")]
+pub use crate::scene::CaptureSceneFilter;
+#[cfg(feature = "sidecar")]
+pub use crate::sidecar::{CaptureMetadataSource, CaptureMetadataSources, StateMetadata};
+#[cfg(feature = "state")]
+pub use crate::state::RenderDocAppExt;
+pub use crate::touch::TouchGesture;
+pub use crate::view_scope::CaptureThisView;
+
+/// Capture requests shared between [RenderDocTrigger] and the render world.
+#[derive(Default)]
+struct CaptureRequests {
+    available: AtomicBool,
+    enabled: AtomicBool,
+    frames: AtomicU32,
+    burst_source: Mutex<CaptureSource>,
+    span_start: AtomicBool,
+    span_end: AtomicBool,
+    cancel: AtomicBool,
+    detached: AtomicBool,
+    /// Whether the render world has a capture in progress, see [RenderDocTrigger::is_capturing].
+    capturing: AtomicBool,
+    /// Whether the capture in progress continues into the next frame, as a span or a burst.
+    capture_continues: AtomicBool,
+    queue: Mutex<VecDeque<QueuedCapture>>,
+    max_queued: usize,
+    /// Number of main-world frames that have started, stamped on queued captures.
+    main_frame: AtomicU32,
+    frame_offset: u32,
+    trigger_cooldown: Duration,
+    last_trigger: Mutex<Option<Instant>>,
+    suppressed: Mutex<Vec<CaptureSuppressed>>,
+    scheduled: Mutex<Vec<ScheduledCapture>>,
+    user_comment: Mutex<Option<String>>,
+    target_window: Mutex<Option<Entity>>,
+    saved: Mutex<Vec<CaptureSaved>>,
+    failed: Mutex<Vec<CaptureFailed>>,
+    /// Captures pausing the app once saved, see [RenderDocTrigger::capture_and_pause].
+    pause_after: Mutex<Vec<CaptureHandle>>,
+    options: Mutex<Option<CaptureOptions>>,
+    open_paths: Mutex<Vec<PathBuf>>,
+    open_replay_ui_always: bool,
+    build_info: Option<String>,
+    attach_requested: AtomicBool,
+    /// The loaded API, waiting to be moved into the render world.
+    attached: Mutex<Option<Box<dyn FrameCaptureBackend>>>,
+    /// The RenderDoc API shared with the attached RenderDoc backend, waiting to be inserted into the render world.
+    attached_renderdoc: Mutex<Option<Arc<Mutex<api::RenderDocApi>>>>,
+}
+
+impl CaptureRequests {
+    /// Request a burst of consecutive frame captures, see [RenderDocTrigger::capture_frames].
+    fn request_frames(&self, frames: u32, source: CaptureSource) {
+        *self.burst_source.lock().unwrap() = source;
+        self.frames.fetch_max(frames, Ordering::SeqCst);
+    }
+
+    /// Record that a capture from `source` failed with `reason`, to be sent as a [CaptureFailed] event.
+    fn fail(&self, source: CaptureSource, reason: CaptureError) {
+        // Captures are expected to be rejected once RenderDoc has been detached on purpose.
+        if self.detached.load(Ordering::SeqCst) {
+            return;
+        }
+
+        warn!("{:?} capture failed: {}", source, reason);
+        self.failed
+            .lock()
+            .unwrap()
+            .push(CaptureFailed { reason, source });
+    }
+
+    /// Whether the next extraction starts or continues a capture, see [DuringCapture].
+    fn next_frame_captured(&self) -> bool {
+        // Requests made before a backend is attached wait for it instead.
+        if !self.available.load(Ordering::SeqCst) {
+            return false;
+        }
+
+        if self.capture_continues.load(Ordering::SeqCst)
+            || self.frames.load(Ordering::SeqCst) > 0
+            || self.span_start.load(Ordering::SeqCst)
+        {
+            return true;
+        }
+
+        let frame = self.main_frame.load(Ordering::SeqCst);
+        self.queue.lock().unwrap().front().is_some_and(|capture| {
+            capture.requested_frame.saturating_add(self.frame_offset) <= frame
+        })
+    }
+
+    /// Hand an attached backend over to the render world, and start accepting capture requests.
+    fn attach(&self, backend: Box<dyn FrameCaptureBackend>) {
+        *self.attached.lock().unwrap() = Some(backend);
+        self.available.store(true, Ordering::SeqCst);
+    }
+}
+
+/// A capture of a single frame, waiting for a frame of its own.
+struct QueuedCapture {
+    handle: Arc<CaptureHandleState>,
+    source: CaptureSource,
+    title: Option<String>,
+    window: Option<Entity>,
+    open_replay_ui: bool,
+    /// The main-world frame the capture was requested in.
+    requested_frame: u32,
+    /// The entity that armed the capture, see [CaptureOnSpawn].
+    entity: Option<Entity>,
+    /// The Ctrl+clicked entity and its asset handles, with the `picking` feature.
+    picked: Option<String>,
+    /// A comment attached to this capture only, e.g. the validation error that armed it.
+    comment: Option<String>,
+}
+
+impl QueuedCapture {
+    fn new(handle: Arc<CaptureHandleState>, source: CaptureSource) -> Self {
+        Self {
+            handle,
+            source,
+            title: None,
+            window: None,
+            open_replay_ui: false,
+            requested_frame: 0,
+            entity: None,
+            picked: None,
+            comment: None,
+        }
+    }
+}
+
+/// A capture armed for a later frame.
+struct ScheduledCapture {
+    frame: ScheduledFrame,
+    capture: QueuedCapture,
+}
+
+enum ScheduledFrame {
+    /// A number of frames after the current one, not yet resolved to a frame number.
+    In(u32),
+    /// A specific [FrameCount].
+    At(u32),
+}
+
+#[derive(Resource)]
+struct RenderDocData {
+    requests: Arc<CaptureRequests>,
+    frames_remaining: u32,
+    is_capture_active: bool,
+    is_span_active: bool,
+    capture_target: CaptureTarget,
+    frames_in_capture: u32,
+    active_handle: Option<Arc<CaptureHandleState>>,
+    active_source: CaptureSource,
+    active_metadata: CaptureMetadata,
+    capture_started: Instant,
+    burst_source: CaptureSource,
+    open_replay_ui_always: bool,
+    open_replay_ui_pending: bool,
+    backend: Mutex<Box<dyn FrameCaptureBackend>>,
+}
+
+/// Minimum interval between captures triggered by input, unless configured otherwise.
+const DEFAULT_TRIGGER_COOLDOWN: Duration = Duration::from_millis(500);
+
+/// Number of capture requests that can be queued before new ones are rejected, unless configured otherwise.
+const DEFAULT_MAX_QUEUED_CAPTURES: usize = 16;
+
+/// The RenderDoc capture plugin.
+///
+/// It can be added before or after `DefaultPlugins`, as the render world is only set up once all plugins are built.
+pub struct RenderDocPlugin {
+    binding: Option<TriggerBinding>,
+    open_replay_ui: bool,
+    capture_options: CaptureOptions,
+    startup_frames: u32,
+    periodic_capture: Option<(Duration, Option<u32>)>,
+    spike_capture: Option<SpikeCapture>,
+    validation_error_capture: bool,
+    capture_path: Option<PathBuf>,
+    overlay: Option<RenderDocOverlaySettings>,
+    enabled: bool,
+    max_queued_captures: usize,
+    capture_frame_offset: u32,
+    trigger_cooldown: Duration,
+    consume_trigger: bool,
+    require_focus: bool,
+    attach_retry: Option<Duration>,
+    library_path: Option<PathBuf>,
+    exit_after_capture: Option<u32>,
+    #[cfg(feature = "signal")]
+    signal_trigger: bool,
+    trigger_file: Option<PathBuf>,
+    touch_gesture: Option<TouchGesture>,
+    stdin_commands: bool,
+    build_info: Option<String>,
+    retention: retention::CaptureRetention,
+    capture_saved_hooks: Vec<CaptureSavedHook>,
+    trigger_conditions: Mutex<Vec<TriggerCondition>>,
+    capture_backend: Mutex<Option<Box<dyn FrameCaptureBackend>>>,
+    renderdoc_hotkeys: bool,
+    focus_toggle_keys: Option<Vec<KeyCode>>,
+    unload_crash_handler: bool,
+    node_debug_groups: bool,
+    label_gpu_resources: bool,
+    shader_debug_info: bool,
+    wgpu_validation: bool,
+    deterministic_delta: Option<Duration>,
+    frame_step_key: Option<KeyCode>,
+    strict: bool,
+    #[cfg(feature = "compress")]
+    compress_captures: Option<bool>,
+    #[cfg(feature = "remote")]
+    http_port: Option<u16>,
+}
+
+/// A callback added with [RenderDocPluginBuilder::on_capture_saved].
+type CaptureSavedHook = Arc<dyn Fn(&CaptureSaved) + Send + Sync>;
+
+/// Adds a system capturing when a condition passed to [RenderDocPluginBuilder::trigger_condition] starts holding.
+type TriggerCondition = Box<dyn FnOnce(&mut App) + Send>;
+
+/// The callbacks added with [RenderDocPluginBuilder::on_capture_saved].
+#[derive(Resource)]
+struct CaptureSavedHooks(Vec<CaptureSavedHook>);
+
+/// Plugin settings that can be overridden by a config file, see the `config` feature.
+struct FileSettings {
+    binding: Option<TriggerBinding>,
+    capture_path: Option<PathBuf>,
+    overlay: Option<RenderDocOverlaySettings>,
+    capture_options: CaptureOptions,
+}
+
+/// Configuration and state for [RenderDocPluginBuilder::spike_capture].
+#[derive(Resource, Clone, Copy)]
+struct SpikeCapture {
+    threshold: Duration,
+    cooldown: Duration,
+    last_capture: Option<Duration>,
+}
+
+/// Timer for [RenderDocPluginBuilder::periodic_capture].
+#[derive(Resource)]
+struct PeriodicCapture {
+    timer: Timer,
+    remaining: Option<u32>,
+}
+
+/// An input that triggers a capture when pressed.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Debug, PartialEq)]
+pub enum TriggerBinding {
+    /// A keyboard key.
+    Key(KeyCode),
+    /// A button on any connected gamepad.
+    Gamepad(GamepadButton),
+    /// A keyboard key, pressed while holding exactly the given modifiers.
+    Chord {
+        /// Modifiers that must be held.
+        modifiers: KeyModifiers,
+        /// The key completing the chord.
+        key: KeyCode,
+    },
+    /// A mouse button, e.g. [MouseButton::Back] on mice with side buttons.
+    Mouse(MouseButton),
+    /// A mouse button, pressed while holding exactly the given modifiers.
+    MouseChord {
+        /// Modifiers that must be held.
+        modifiers: KeyModifiers,
+        /// The mouse button completing the chord.
+        button: MouseButton,
+    },
+}
+
+/// Modifier keys held as part of a [TriggerBinding::Chord] or [TriggerBinding::MouseChord].
+///
+/// Left and right variants are treated the same.
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Debug, Default, PartialEq)]
+pub struct KeyModifiers {
+    /// Either control key.
+    pub control: bool,
+    /// Either shift key.
+    pub shift: bool,
+    /// Either alt key.
+    pub alt: bool,
+    /// Either super key, e.g. the Windows or Command key.
+    pub super_key: bool,
+}
+
+impl KeyModifiers {
+    /// The modifiers currently held.
+    fn pressed(keys: &ButtonInput<KeyCode>) -> Self {
+        Self {
+            control: keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]),
+            shift: keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+            alt: keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]),
+            super_key: keys.any_pressed([KeyCode::SuperLeft, KeyCode::SuperRight]),
+        }
+    }
+}
+
+impl TriggerBinding {
+    /// Whether the binding was pressed this frame.
+    fn just_pressed<'a>(
+        &self,
+        keys: &ButtonInput<KeyCode>,
+        mouse: Option<&ButtonInput<MouseButton>>,
+        mut gamepads: impl Iterator<Item = &'a Gamepad>,
+    ) -> bool {
+        let mouse_pressed = |button| mouse.is_some_and(|mouse| mouse.just_pressed(button));
+
+        match *self {
+            Self::Key(key_code) => keys.just_pressed(key_code),
+            Self::Gamepad(button) => gamepads.any(|gamepad| gamepad.just_pressed(button)),
+            Self::Chord { modifiers, key } => {
+                keys.just_pressed(key) && KeyModifiers::pressed(keys) == modifiers
+            }
+            Self::Mouse(button) => mouse_pressed(button),
+            Self::MouseChord { modifiers, button } => {
+                mouse_pressed(button) && KeyModifiers::pressed(keys) == modifiers
+            }
+        }
+    }
+
+    /// Reset the binding's key or button, so it doesn't read as pressed for the rest of the frame.
+    fn consume(
+        &self,
+        keys: &mut ButtonInput<KeyCode>,
+        mouse: Option<&mut ButtonInput<MouseButton>>,
+        gamepads: &mut Query<&mut Gamepad>,
+    ) {
+        match *self {
+            Self::Key(key) | Self::Chord { key, .. } => keys.reset(key),
+            Self::Gamepad(button) => {
+                for mut gamepad in gamepads {
+                    gamepad.digital_mut().reset(button);
+                }
+            }
+            Self::Mouse(button) | Self::MouseChord { button, .. } => {
+                if let Some(mouse) = mouse {
+                    mouse.reset(button);
+                }
+            }
+        }
+    }
+}
+
+impl From<KeyCode> for TriggerBinding {
+    fn from(key_code: KeyCode) -> Self {
+        Self::Key(key_code)
+    }
+}
+
+impl From<GamepadButton> for TriggerBinding {
+    fn from(button: GamepadButton) -> Self {
+        Self::Gamepad(button)
+    }
+}
+
+impl From<MouseButton> for TriggerBinding {
+    fn from(button: MouseButton) -> Self {
+        Self::Mouse(button)
+    }
+}
+
+/// System sets bounding a capture in the render world.
+///
+/// [RenderDocCaptureSet::Start] runs in [ExtractSchedule] and [RenderDocCaptureSet::End] runs in [Render] between
+/// [RenderSet::Render] and [RenderSet::Cleanup], so render-world work ordered between them is recorded in the capture.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy::render::{Render, RenderApp, RenderSet};
+/// # use bevy_renderdoc_capture::*;
+/// # let mut app = App::new();
+/// # fn readback() {}
+/// app.sub_app_mut(RenderApp).add_systems(
+///     Render,
+///     readback
+///         .after(RenderSet::Render)
+///         .before(RenderDocCaptureSet::End),
+/// );
+/// ```
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderDocCaptureSet {
+    /// Starts requested captures.
+    Start,
+    /// Ends the capture in progress once the frame has rendered.
+    End,
+}
+
+/// System set in [Last] running only on frames that are about to be captured, to change the scene for captures, e.g.
+/// swapping in debug materials or hiding UI. Changes are reverted in [AfterCapture].
+///
+/// It runs after every capture request of the frame has been made, right before the frame is extracted, so the
+/// captured frame contains what its systems change. Changes that only reach the render world through systems in
+/// [PostUpdate], such as transform propagation or asset events, show up a frame late, so they're best made to
+/// components the render world extracts directly. Spans and bursts run the set on each of their frames.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// # let mut app = App::new();
+/// # fn show_debug_meshes() {}
+/// # fn hide_debug_meshes() {}
+/// app.add_systems(Last, show_debug_meshes.in_set(DuringCapture))
+///     .add_systems(Last, hide_debug_meshes.in_set(AfterCapture));
+/// ```
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DuringCapture;
+
+/// System set in [Last] running once on the first frame after the frames [DuringCapture] ran on, to revert their
+/// changes.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AfterCapture;
+
+/// Whether [DuringCapture] ran this frame and the frame before.
+#[derive(Resource, Default)]
+struct CaptureFrame {
+    captured: bool,
+    previously_captured: bool,
+}
+
+/// Main-world systems of the plugin, which stop running once RenderDoc is detached with [RenderDocTrigger::detach].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct RenderDocSystems;
+
+/// Resource holding the settings of the default hook, which can be changed at runtime, e.g. from a keybinding menu.
+///
+/// Initialized from the binding configured on [RenderDocPlugin].
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// pub fn rebind_capture(mut settings: ResMut<RenderDocSettings>) {
+///     settings.trigger = Some(TriggerBinding::Key(KeyCode::F9));
+/// }
+/// ```
+#[derive(Resource, Reflect, Debug, Clone, PartialEq, Eq)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct RenderDocSettings {
+    /// The input triggering a capture, or `None` to disable the default hook.
+    pub trigger: Option<TriggerBinding>,
+    /// Let the application enter fullscreen mode, re-applied whenever changed. `None` keeps RenderDoc's setting.
+    ///
+    /// Initialized from [CaptureOptions::allow_fullscreen].
+    pub allow_fullscreen: Option<bool>,
+    /// Let the application enable vertical synchronization, re-applied whenever changed. `None` keeps RenderDoc's
+    /// setting.
+    ///
+    /// Initialized from [CaptureOptions::allow_vsync].
+    pub allow_vsync: Option<bool>,
+    /// Reset the binding's key or button once it triggers a capture, so the rest of the app doesn't see it pressed,
+    /// e.g. when the key also toggles the game's HUD. Modifiers of a chord are left alone.
+    pub consume_trigger: bool,
+    /// Ignore the binding while the primary window is unfocused, e.g. when switching to RenderDoc and pressing the
+    /// same key there. Apps without a primary window aren't affected.
+    pub require_focus: bool,
+}
+
+impl Default for RenderDocSettings {
+    fn default() -> Self {
+        Self {
+            trigger: Some(TriggerBinding::Key(KeyCode::F10)),
+            allow_fullscreen: None,
+            allow_vsync: None,
+            consume_trigger: false,
+            require_focus: false,
+        }
+    }
+}
+
+/// RenderDoc capture options, applied when the plugin is built.
+///
+/// Options left as `None` keep the value RenderDoc was configured with, e.g. from its launch dialog.
+#[derive(Reflect, Debug, Clone, Default, PartialEq, Eq)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "config",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct CaptureOptions {
+    /// Record API debugging events and messages. Must be set before the render device is created.
+    pub api_validation: Option<bool>,
+    /// Capture CPU callstacks for API events.
+    pub capture_callstacks: Option<bool>,
+    /// When capturing callstacks, only capture them for draw calls.
+    pub capture_callstacks_only_draws: Option<bool>,
+    /// Include all resources alive at capture time, rather than only those used by the frame.
+    pub ref_all_resources: Option<bool>,
+    /// Verify writes to mapped buffers by checking the memory past the end of the mapping.
+    pub verify_buffer_access: Option<bool>,
+    /// Let the application enable vertical synchronization.
+    pub allow_vsync: Option<bool>,
+    /// Let the application enter fullscreen mode.
+    pub allow_fullscreen: Option<bool>,
+    /// Mute the API's debug output while [CaptureOptions::api_validation] is enabled.
+    pub debug_output_mute: Option<bool>,
+    /// Inject RenderDoc into child processes launched by the application, e.g. helper render processes or a game
+    /// relaunching itself, so they can be captured too.
+    pub hook_into_children: Option<bool>,
+    /// Capture all command lists recorded since the application started, rather than only those recorded once a
+    /// capture is triggered. Ignored by APIs that always do so, such as Vulkan and D3D12.
+    pub capture_all_cmd_lists: Option<bool>,
+    /// Memory, in megabytes, that some APIs try to keep RenderDoc's overhead under, `0` for no limit.
+    pub soft_memory_limit: Option<u32>,
+}
+
+/// Event sent in the main world after RenderDoc has saved a capture started by this plugin.
+///
+/// Its index, frame and timestamp are logged too, to cross-reference logs with captures.
+#[derive(Event, Debug, Clone)]
+pub struct CaptureSaved {
+    /// Index of the capture within this RenderDoc session.
+    pub index: u32,
+    /// Path of the saved capture file.
+    pub path: PathBuf,
+    /// Number of frames recorded in the capture.
+    pub frame_count: u32,
+    /// The [FrameCount] of the first captured frame, if [FrameCount] is available.
+    pub frame: Option<u32>,
+    /// When RenderDoc saved the capture.
+    pub timestamp: SystemTime,
+    /// How long the capture was active for, including writing it to disk.
+    pub duration: Duration,
+    /// The window the capture was taken from, or `None` if no window was being rendered, e.g. in a headless app.
+    pub window: Option<Entity>,
+    /// What triggered the capture.
+    pub source: CaptureSource,
+}
+
+/// Event sent in the main world when a capture fails without a capture file, e.g. because RenderDoc isn't attached
+/// or didn't save it, so automation doesn't silently end up without captures.
+///
+/// Captures that are cancelled, disabled or suppressed by the cooldown aren't failures. With
+/// [RenderDocPluginBuilder::strict], the app panics instead.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct CaptureFailed {
+    /// Why the capture failed.
+    pub reason: CaptureError,
+    /// What triggered the capture.
+    pub source: CaptureSource,
+}
+
+/// Event sent in the main world when the app is paused after a capture requested with
+/// [RenderDocTrigger::capture_and_pause].
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct PausedAfterCapture {
+    /// Path of the saved capture.
+    pub path: PathBuf,
+}
+
+/// Marks that [PausedAfterCapture] enters a state rather than pausing [`Time<Virtual>`].
+#[derive(Resource)]
+pub(crate) struct PauseWithState;
+
+/// Event sent in the main world once a [FrameCaptureBackend] is attached, naming the one captures are taken with.
+///
+/// Backends are probed when the plugin is built, and the first one available is used: a backend set with
+/// [RenderDocPluginBuilder::capture_backend], then PIX, RenderDoc and Metal, for those compiled in.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct CaptureBackendSelected {
+    /// Name of the backend, as reported in [RenderDocStatus::backend].
+    pub backend: String,
+}
+
+/// Event sent in the main world once the renderer is set up, if it renders with a graphics backend the attached
+/// [FrameCaptureBackend] can't capture, e.g. Metal with RenderDoc, so captures would be empty.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedGraphicsBackend {
+    /// The graphics backend the app renders with, e.g. `Metal`.
+    pub graphics_backend: String,
+    /// How to capture it instead, e.g. with another capture backend compiled in.
+    pub suggestion: String,
+}
+
+/// Event sent in the main world when a capture triggered by input is suppressed, as it came too soon after the
+/// previous one, see [RenderDocPluginBuilder::trigger_cooldown].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureSuppressed {
+    /// What triggered the suppressed capture.
+    pub source: CaptureSource,
+    /// How long until captures can be triggered again.
+    pub remaining: Duration,
+}
+
+/// What triggered a capture.
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[reflect(Debug, Default, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum CaptureSource {
+    /// A call to a [RenderDocTrigger] method.
+    #[default]
+    Manual,
+    /// The default hook's binding, see [RenderDocSettings].
+    Binding,
+    /// A [RequestCapture] event, or a triggered [RenderDocCapture].
+    Event,
+    /// [RenderDocTrigger::capture_in] or [RenderDocTrigger::capture_at_frame].
+    Scheduled,
+    /// [RenderDocTrigger::capture_frames] or [RenderDocTrigger::capture_burst].
+    Burst,
+    /// [RenderDocTrigger::start_capture].
+    Span,
+    /// [RenderDocPluginBuilder::startup_capture_frames].
+    Startup,
+    /// [RenderDocPluginBuilder::periodic_capture].
+    Periodic,
+    /// [RenderDocPluginBuilder::spike_capture].
+    Spike,
+    /// [RenderDocPluginBuilder::validation_error_capture].
+    ValidationError,
+    /// [RenderDocPluginBuilder::signal_trigger].
+    Signal,
+    /// [RenderDocPluginBuilder::trigger_file].
+    File,
+    /// [RenderDocPluginBuilder::trigger_condition].
+    Condition,
+    /// Entering a state, see `RenderDocAppExt` with the `state` feature.
+    StateEnter,
+    /// Adding [CaptureOnSpawn] to an entity.
+    Spawn,
+    /// [RenderDocPluginBuilder::touch_trigger].
+    Touch,
+    /// [RenderDocPluginBuilder::frame_step_key].
+    FrameStep,
+    /// A leafwing-input-manager action, see `RenderDocPluginBuilder::leafwing_action` with the `leafwing` feature.
+    Action,
+    /// Ctrl+clicking an entity, with the `picking` feature.
+    Picking,
+    /// [RenderDocCaptureRequests::arm_for_next_frame] in the render world.
+    RenderWorld,
+}
+
+/// Resource listing every capture saved this session, oldest first.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// pub fn list_recent_captures(history: Res<CaptureHistory>) {
+///     for capture in history.recent(5) {
+///         info!("{:?} capture: {}", capture.source, capture.path.display());
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CaptureHistory {
+    captures: Vec<CaptureSaved>,
+}
+
+impl CaptureHistory {
+    /// All captures saved this session, oldest first.
+    pub fn captures(&self) -> &[CaptureSaved] {
+        &self.captures
+    }
+
+    /// Up to `count` of the most recent captures, newest first.
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &CaptureSaved> {
+        self.captures.iter().rev().take(count)
+    }
+
+    /// The most recently saved capture.
+    pub fn latest(&self) -> Option<&CaptureSaved> {
+        self.captures.last()
+    }
+}
+
+/// Resource describing whether RenderDoc is attached to the application.
+///
+/// Always inserted into the main world by [RenderDocPlugin], even if RenderDoc could not be loaded.
+#[derive(Resource, Reflect, Debug, Clone, Default, PartialEq, Eq)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct RenderDocStatus {
+    /// Whether RenderDoc was loaded and the capture hook is registered.
+    pub available: bool,
+    /// The `(major, minor, patch)` version of the attached RenderDoc API.
+    ///
+    /// Newer entry points are used when available, and features needing them are skipped on older versions.
+    pub api_version: Option<(u32, u32, u32)>,
+    /// Path of the loaded RenderDoc library, if it could be determined.
+    pub library_path: Option<PathBuf>,
+    /// The path template captures are saved with.
+    pub capture_path_template: Option<PathBuf>,
+    /// Name of the [FrameCaptureBackend] captures are taken with, e.g. `RenderDoc`, or `None` if none was found.
+    pub backend: Option<String>,
+    /// The graphics backend the app renders with, if the capture backend can't capture it, see
+    /// [UnsupportedGraphicsBackend].
+    pub unsupported_graphics_backend: Option<String>,
+}
+
+/// Render-world resource giving direct access to the RenderDoc in-application API, for entry points the plugin
+/// doesn't wrap.
+///
+/// Only present while RenderDoc is the attached capture backend. The plugin still starts and ends captures itself, so
+/// starting, ending or triggering captures through the API bypasses its queue and events.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// pub fn log_active_captures(renderdoc: Option<Res<RenderDocApi>>) {
+///     let Some(renderdoc) = renderdoc else {
+///         return;
+///     };
+///
+///     if renderdoc.with_api(|api| api.is_frame_capturing()) {
+///         info!("RenderDoc is capturing");
+///     }
+/// }
+/// ```
+#[cfg(all(
+    feature = "renderdoc",
+    not(any(target_arch = "wasm32", target_vendor = "apple"))
+))]
+#[derive(Resource, Clone)]
+pub struct RenderDocApi {
+    api: Arc<Mutex<api::RenderDocApi>>,
+}
+
+#[cfg(all(
+    feature = "renderdoc",
+    not(any(target_arch = "wasm32", target_vendor = "apple"))
+))]
+impl RenderDocApi {
+    /// Call `f` with the [RenderDoc](renderdoc::RenderDoc) API, holding the plugin's lock on it for the duration of
+    /// the call.
+    ///
+    /// Entry points common to every API version are available, the attached version being reported in
+    /// [RenderDocStatus::api_version].
+    pub fn with_api<R>(
+        &self,
+        f: impl FnOnce(&mut renderdoc::RenderDoc<renderdoc::V100>) -> R,
+    ) -> R {
+        let mut api = self.api.lock().unwrap();
+        f(&mut api)
+    }
+}
+
+/// Run condition that is true if RenderDoc is attached to the application.
+pub fn renderdoc_available(status: Option<Res<RenderDocStatus>>) -> bool {
+    status.is_some_and(|status| status.available)
+}
+
+/// Run condition that is true while a capture is in progress, see [RenderDocTrigger::is_capturing].
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// # fn draw_debug_wireframes() {}
+/// # let mut app = App::new();
+/// app.add_systems(Update, draw_debug_wireframes.run_if(in_renderdoc_capture));
+/// ```
+pub fn in_renderdoc_capture(trigger: Option<Res<RenderDocTrigger>>) -> bool {
+    trigger.is_some_and(|trigger| trigger.is_capturing())
+}
+
+/// Event requesting a capture of the next frame, as an alternative to calling [RenderDocTrigger::capture].
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// pub fn my_system(mut requests: EventWriter<RequestCapture>) {
+///
+///     // Do some checks...
+///
+///     requests.write(RequestCapture);
+/// }
+/// ```
+#[derive(Event, Debug, Clone, Copy, Default)]
+pub struct RequestCapture;
+
+/// Event triggering a capture through an observer, so captures can be requested from any command context, e.g. UI
+/// callbacks.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// pub fn my_system(mut commands: Commands) {
+///     commands.trigger(RenderDocCapture {
+///         frames: 1,
+///         name: Some("Level loaded".to_owned()),
+///     });
+/// }
+/// ```
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct RenderDocCapture {
+    /// Number of consecutive frames to capture, each saved as an individual capture, see
+    /// [RenderDocTrigger::capture_frames].
+    pub frames: u32,
+    /// Title of the capture, see [RenderDocTrigger::capture_named]. Only used when capturing a single frame.
+    pub name: Option<String>,
+}
+
+impl Default for RenderDocCapture {
+    fn default() -> Self {
+        Self {
+            frames: 1,
+            name: None,
+        }
+    }
+}
+
+/// Component arming a capture when added to an entity, e.g. to capture the frame a particular effect spawns in.
+///
+/// The entity, along with its [Name] if it has one, is recorded in the capture's comments.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// pub fn spawn_explosion(mut commands: Commands) {
+///     commands.spawn((Name::new("Explosion"), CaptureOnSpawn::default()));
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub struct CaptureOnSpawn {
+    /// Capture this many frames after the component is added rather than the frame it's added in, see
+    /// [RenderDocTrigger::capture_in].
+    pub delay_frames: u32,
+}
+
+/// Resource controlling which parts of RenderDoc's in-application overlay are shown.
+///
+/// Changes are applied to RenderDoc at the start of the next rendered frame.
+#[derive(Resource, ExtractResource, Reflect, Debug, Clone, PartialEq, Eq)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct RenderDocOverlaySettings {
+    /// Whether the overlay is shown at all.
+    pub enabled: bool,
+    /// Show the average, minimum and maximum frame rate.
+    pub frame_rate: bool,
+    /// Show the current frame number.
+    pub frame_number: bool,
+    /// Show the list of recent captures.
+    pub capture_list: bool,
+}
+
+impl Default for RenderDocOverlaySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            frame_rate: true,
+            frame_number: true,
+            capture_list: true,
+        }
+    }
+}
+
+/// Resource for triggering a renderdoc capture using [RenderDocTrigger::capture].
+///
+/// Triggers are handled during [ExtractSchedule] and you should enable capture prior.
+///
+/// By default, a hook is registered that triggers a renderdoc capture upon pressing F10.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// pub fn my_system(trigger: Res<RenderDocTrigger>) {
+///
+///     // Do some checks...
+///
+///     trigger.capture();
+/// }
+/// ```
+#[derive(Resource)]
+pub struct RenderDocTrigger {
+    requests: Arc<CaptureRequests>,
+}
+
+impl RenderDocTrigger {
+    /// Trigger capturing the next frame for RenderDoc.
+    ///
+    /// Each call captures its own frame: if a capture is already pending or in progress, this one is queued for a
+    /// following frame. The returned [CaptureHandle] resolves once the capture has been saved, and can be ignored
+    /// otherwise.
+    ///
+    /// This operation cannot be undone.
+    pub fn capture(&self) -> CaptureHandle {
+        self.capture_from(CaptureSource::Manual)
+    }
+
+    /// Trigger capturing the next frame, recording `source` as what triggered it.
+    fn capture_from(&self, source: CaptureSource) -> CaptureHandle {
+        self.capture_from_with(source, |_| {})
+    }
+
+    /// Trigger capturing the next frame from `source`, letting `configure` set its per-capture options unless it's
+    /// suppressed by the trigger cooldown.
+    fn capture_from_with(
+        &self,
+        source: CaptureSource,
+        configure: impl FnOnce(&mut QueuedCapture),
+    ) -> CaptureHandle {
+        if let Some(remaining) = self.cooldown_remaining(source) {
+            debug!(
+                "Suppressing RenderDoc capture from {:?} during the trigger cooldown",
+                source
+            );
+            let suppressed = CaptureSuppressed { source, remaining };
+            self.requests.suppressed.lock().unwrap().push(suppressed);
+
+            let (handle, state) = CaptureHandle::new();
+            state.resolve(Err(CaptureError::Cooldown));
+            return handle;
+        }
+
+        self.queue_capture(source, configure)
+    }
+
+    /// How long captures from `source` are still suppressed for, or `None` if it isn't debounced or the cooldown is
+    /// over, in which case a new cooldown starts.
+    fn cooldown_remaining(&self, source: CaptureSource) -> Option<Duration> {
+        let debounced = matches!(
+            source,
+            CaptureSource::Binding
+                | CaptureSource::Action
+                | CaptureSource::Signal
+                | CaptureSource::File
+                | CaptureSource::Touch
+                | CaptureSource::ValidationError
+        );
+        if !debounced || self.requests.trigger_cooldown.is_zero() {
+            return None;
+        }
+
+        let mut last_trigger = self.requests.last_trigger.lock().unwrap();
+        let now = Instant::now();
+        let elapsed = last_trigger.map(|last| now.duration_since(last));
+        match elapsed {
+            Some(elapsed) if elapsed < self.requests.trigger_cooldown => {
+                Some(self.requests.trigger_cooldown - elapsed)
+            }
+            _ => {
+                *last_trigger = Some(now);
+                None
+            }
+        }
+    }
+
+    /// Queue a capture of a single frame, letting `configure` set its per-capture options.
+    fn queue_capture(
+        &self,
+        source: CaptureSource,
+        configure: impl FnOnce(&mut QueuedCapture),
+    ) -> CaptureHandle {
+        let (handle, state) = CaptureHandle::new();
+        let mut capture = QueuedCapture::new(state, source);
+        configure(&mut capture);
+        self.request_capture(capture);
+        handle
+    }
+
+    /// Trigger capturing the next frame, and pause [`Time<Virtual>`] once the capture has been saved, so the scene
+    /// stays as it was captured while the capture is inspected. Resume with [Time::unpause].
+    ///
+    /// With the `state` feature, `RenderDocAppExt::add_renderdoc_pause_state` enters a state instead. The app keeps
+    /// running if the capture fails. With pipelined rendering, the main world runs one more frame before the capture
+    /// is saved.
+    pub fn capture_and_pause(&self) -> CaptureHandle {
+        let handle = self.capture();
+        self.requests
+            .pause_after
+            .lock()
+            .unwrap()
+            .push(handle.clone());
+        handle
+    }
+
+    /// Trigger capturing the frame `frames` frames after the current one.
+    ///
+    /// Relies on [FrameCount], which is provided by `DefaultPlugins` and `MinimalPlugins`.
+    pub fn capture_in(&self, frames: u32) -> CaptureHandle {
+        self.schedule(ScheduledFrame::In(frames), CaptureSource::Scheduled, |_| {})
+    }
+
+    /// Trigger capturing the frame at which [FrameCount] equals `frame`, or the next frame if it has already passed.
+    ///
+    /// Relies on [FrameCount], which is provided by `DefaultPlugins` and `MinimalPlugins`.
+    pub fn capture_at_frame(&self, frame: u32) -> CaptureHandle {
+        self.schedule(ScheduledFrame::At(frame), CaptureSource::Scheduled, |_| {})
+    }
+
+    /// Arm a capture for a later frame, letting `configure` set its per-capture options.
+    fn schedule(
+        &self,
+        frame: ScheduledFrame,
+        source: CaptureSource,
+        configure: impl FnOnce(&mut QueuedCapture),
+    ) -> CaptureHandle {
+        let (handle, state) = CaptureHandle::new();
+
+        match self.rejection() {
+            Some(error) => {
+                self.fail_rejected(source, &error);
+                state.resolve(Err(error));
+            }
+            None => {
+                let mut capture = QueuedCapture::new(state, source);
+                configure(&mut capture);
+                let scheduled = ScheduledCapture { frame, capture };
+                self.requests.scheduled.lock().unwrap().push(scheduled);
+            }
+        }
+
+        handle
+    }
+
+    fn request_capture(&self, mut capture: QueuedCapture) {
+        if let Some(error) = self.rejection() {
+            self.fail_rejected(capture.source, &error);
+            capture.handle.resolve(Err(error));
+            return;
+        }
+
+        let mut queue = self.requests.queue.lock().unwrap();
+        if queue.len() >= self.requests.max_queued {
+            drop(queue);
+            self.requests.fail(capture.source, CaptureError::QueueFull);
+            capture.handle.resolve(Err(CaptureError::QueueFull));
+            return;
+        }
+
+        capture.requested_frame = self.requests.main_frame.load(Ordering::SeqCst);
+        queue.push_back(capture);
+    }
+
+    /// Enable or disable captures. While disabled, the default hook does nothing and new capture requests are
+    /// ignored, with their [CaptureHandle] resolving to [CaptureError::Disabled].
+    ///
+    /// Captures already in progress are not affected.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.requests.enabled.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Whether captures are enabled, see [RenderDocTrigger::set_enabled].
+    pub fn is_enabled(&self) -> bool {
+        self.requests.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Whether a capture is in progress in the render world.
+    ///
+    /// This is updated as the render world starts and ends captures, so with pipelined rendering a single-frame
+    /// capture may already be over by the time the main world sees it. Multi-frame and span captures are seen for
+    /// their whole duration.
+    pub fn is_capturing(&self) -> bool {
+        self.requests.capturing.load(Ordering::SeqCst)
+    }
+
+    /// Try attaching RenderDoc at the start of the next frame, if it wasn't attached when the plugin was built, e.g.
+    /// after injecting RenderDoc into the running process.
+    ///
+    /// [RenderDocStatus] is updated once RenderDoc is attached. Note that RenderDoc can generally only capture
+    /// devices created after it was injected.
+    pub fn try_attach(&self) {
+        self.requests.attach_requested.store(true, Ordering::SeqCst);
+    }
+
+    /// The number of captures queued behind the one in progress, waiting for a frame of their own.
+    pub fn queued_captures(&self) -> usize {
+        self.requests.queue.lock().unwrap().len()
+    }
+
+    /// Report a capture request rejected with `error` as failed, unless captures were disabled on purpose.
+    fn fail_rejected(&self, source: CaptureSource, error: &CaptureError) {
+        if *error == CaptureError::Unavailable {
+            self.requests.fail(source, CaptureError::Unavailable);
+        }
+    }
+
+    /// Why a new capture request can't be handled, or `None` if it can.
+    fn rejection(&self) -> Option<CaptureError> {
+        if !self.requests.available.load(Ordering::SeqCst) {
+            Some(CaptureError::Unavailable)
+        } else if !self.is_enabled() {
+            Some(CaptureError::Disabled)
+        } else {
+            None
+        }
+    }
+
+    /// Trigger capturing the next `frames` frames for RenderDoc, each saved as an individual capture.
+    ///
+    /// If a burst is already pending or in progress, the longer of the two is kept. Ignored while captures are
+    /// disabled.
+    ///
+    /// This operation cannot be undone.
+    pub fn capture_frames(&self, frames: u32) {
+        self.capture_frames_from(frames, CaptureSource::Burst);
+    }
+
+    /// Trigger capturing the next `frames` frames, recording `source` as what triggered them.
+    fn capture_frames_from(&self, frames: u32, source: CaptureSource) {
+        match self.rejection() {
+            Some(error) => self.fail_rejected(source, &error),
+            None => self.requests.request_frames(frames, source),
+        }
+    }
+
+    /// Trigger capturing every `every`th frame, `count` times, starting with the current frame, e.g. to sample how
+    /// accumulation buffers or global illumination converge over a second of gameplay with a single trigger.
+    ///
+    /// Each frame is saved as an individual capture, with a [CaptureHandle] of its own. Relies on [FrameCount], which
+    /// is provided by `DefaultPlugins` and `MinimalPlugins`. An interval of 0 is treated as 1, capturing consecutive
+    /// frames.
+    pub fn capture_burst(&self, every: u32, count: u32) -> Vec<CaptureHandle> {
+        let every = every.max(1);
+        (0..count)
+            .map(|index| {
+                let frame = ScheduledFrame::In(index.saturating_mul(every));
+                self.schedule(frame, CaptureSource::Burst, |_| {})
+            })
+            .collect()
+    }
+
+    /// Start a capture that stays open across frames until [RenderDocTrigger::end_capture] is called.
+    ///
+    /// Frame captures requested while the span is open are held back until it ends. Ignored while captures are
+    /// disabled.
+    pub fn start_capture(&self) {
+        match self.rejection() {
+            Some(error) => self.fail_rejected(CaptureSource::Span, &error),
+            None => self.requests.span_start.store(true, Ordering::SeqCst),
+        }
+    }
+
+    /// End a capture started with [RenderDocTrigger::start_capture], after the current frame has rendered.
+    ///
+    /// Does nothing if no span capture is active.
+    pub fn end_capture(&self) {
+        self.requests.span_end.store(true, Ordering::SeqCst);
+    }
+
+    /// Cancel all pending captures, and discard the capture in progress without saving it.
+    ///
+    /// The [CaptureHandle]s of cancelled captures resolve to [CaptureError::Cancelled]. RenderDoc versions older than
+    /// 1.4 can't discard captures, in which case the capture in progress is saved and then deleted.
+    pub fn cancel(&self) {
+        let requests = &self.requests;
+        requests.frames.store(0, Ordering::SeqCst);
+        requests.span_start.store(false, Ordering::SeqCst);
+        requests.capture_continues.store(false, Ordering::SeqCst);
+
+        let queued = std::mem::take(&mut *requests.queue.lock().unwrap());
+        let scheduled = std::mem::take(&mut *requests.scheduled.lock().unwrap());
+        let handles = queued.into_iter().map(|capture| capture.handle).chain(
+            scheduled
+                .into_iter()
+                .map(|scheduled| scheduled.capture.handle),
+        );
+        for handle in handles {
+            handle.resolve(Err(CaptureError::Cancelled));
+        }
+
+        requests.cancel.store(true, Ordering::SeqCst);
+    }
+
+    /// Detach RenderDoc for the rest of the process, e.g. once a debugging session in a long-running editor is over.
+    ///
+    /// Pending captures are cancelled as with [RenderDocTrigger::cancel], RenderDoc's overlay is hidden, and the
+    /// plugin's systems stop running. [RenderDocStatus::available] becomes `false` and new capture requests resolve to
+    /// [CaptureError::Unavailable]. [RenderDocTrigger::try_attach] does nothing afterwards.
+    ///
+    /// RenderDoc's graphics API hooks stay installed: RenderDoc only supports removing them before any graphics API
+    /// work has happened, which is long past once the app is running.
+    ///
+    /// This operation cannot be undone.
+    pub fn detach(&self) {
+        self.cancel();
+        self.requests.available.store(false, Ordering::SeqCst);
+        self.requests.detached.store(true, Ordering::SeqCst);
+    }
+
+    /// Trigger capturing the next frame of `window` for RenderDoc, instead of the window set with
+    /// [RenderDocTrigger::set_capture_window].
+    ///
+    /// This operation cannot be undone.
+    pub fn capture_window(&self, window: Entity) -> CaptureHandle {
+        self.queue_capture(CaptureSource::Manual, |capture| {
+            capture.window = Some(window)
+        })
+    }
+
+    /// Change RenderDoc's capture options at runtime.
+    ///
+    /// Options affecting device creation, such as [CaptureOptions::api_validation], only take effect for devices
+    /// created afterwards.
+    pub fn set_capture_options(&self, options: CaptureOptions) {
+        *self.requests.options.lock().unwrap() = Some(options);
+    }
+
+    /// Open a saved capture in the RenderDoc replay UI, e.g. one listed in [CaptureHistory].
+    pub fn open_in_replay_ui(&self, path: impl Into<PathBuf>) {
+        self.requests.open_paths.lock().unwrap().push(path.into());
+    }
+
+    /// Set a comment recorded in the metadata of every following capture, e.g. describing what a tester was doing, or
+    /// `None` to stop recording one.
+    pub fn set_comment(&self, comment: Option<String>) {
+        *self.requests.user_comment.lock().unwrap() = comment;
+    }
+
+    /// Set the window that captures are taken from, or `None` to use the primary window.
+    ///
+    /// If the window doesn't exist, RenderDoc captures whichever window it considers active.
+    pub fn set_capture_window(&self, window: Option<Entity>) {
+        *self.requests.target_window.lock().unwrap() = window;
+    }
+
+    /// Trigger capturing the next frame for RenderDoc, titled `title` in the RenderDoc UI.
+    ///
+    /// RenderDoc versions older than 1.6 do not support capture titles, in which case the title is used as the capture
+    /// file name instead.
+    ///
+    /// This operation cannot be undone.
+    pub fn capture_named(&self, title: impl Into<String>) -> CaptureHandle {
+        self.queue_capture(CaptureSource::Manual, |capture| {
+            capture.title = Some(title.into())
+        })
+    }
+
+    /// Trigger capturing the next frame for RenderDoc, and open it in the RenderDoc replay UI once saved.
+    ///
+    /// This operation cannot be undone.
+    pub fn capture_and_open(&self) -> CaptureHandle {
+        self.queue_capture(CaptureSource::Manual, |capture| {
+            capture.open_replay_ui = true
+        })
+    }
+}
+
+/// Render-world resource for triggering captures from render code, e.g. a custom
+/// [Node](bevy::render::render_graph::Node) or a system in [RenderSet::Prepare] noticing an anomaly such as a NaN in
+/// a readback.
+///
+/// It shares its requests with [RenderDocTrigger], so captures armed from the render world are queued, reported and
+/// cancelled like any other. It's missing on targets RenderDoc doesn't support, so it's best accessed as an
+/// [Option].
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy::render::render_graph::{Node, NodeRunError, RenderGraphContext};
+/// # use bevy::render::renderer::RenderContext;
+/// # use bevy_renderdoc_capture::*;
+/// # fn found_nan(world: &World) -> bool { false }
+/// struct NanCheckNode;
+///
+/// impl Node for NanCheckNode {
+///     fn run<'w>(
+///         &self,
+///         _graph: &mut RenderGraphContext,
+///         _render_context: &mut RenderContext<'w>,
+///         world: &'w World,
+///     ) -> Result<(), NodeRunError> {
+///         if found_nan(world) {
+///             if let Some(requests) = world.get_resource::<RenderDocCaptureRequests>() {
+///                 requests.arm_for_next_frame();
+///             }
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone)]
+pub struct RenderDocCaptureRequests {
+    requests: Arc<CaptureRequests>,
+}
+
+impl RenderDocCaptureRequests {
+    /// Capture the next frame extracted to the render world. With pipelined rendering, the main world may already
+    /// be a frame ahead, in which case the frame after it is captured.
+    pub fn arm_for_next_frame(&self) -> CaptureHandle {
+        let trigger = RenderDocTrigger {
+            requests: self.requests.clone(),
+        };
+        trigger.capture_from(CaptureSource::RenderWorld)
+    }
+
+    /// Whether a capture is in progress, e.g. to avoid arming another one for the anomaly being captured.
+    pub fn is_capturing(&self) -> bool {
+        self.requests.capturing.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for RenderDocPlugin {
+    fn default() -> Self {
+        Self {
+            binding: Some(TriggerBinding::Key(KeyCode::F10)),
+            open_replay_ui: false,
+            capture_options: CaptureOptions::default(),
+            startup_frames: 0,
+            periodic_capture: None,
+            spike_capture: None,
+            validation_error_capture: false,
+            capture_path: None,
+            overlay: None,
+            enabled: true,
+            max_queued_captures: DEFAULT_MAX_QUEUED_CAPTURES,
+            capture_frame_offset: 0,
+            trigger_cooldown: DEFAULT_TRIGGER_COOLDOWN,
+            consume_trigger: false,
+            require_focus: false,
+            attach_retry: None,
+            library_path: None,
+            exit_after_capture: None,
+            #[cfg(feature = "signal")]
+            signal_trigger: false,
+            trigger_file: None,
+            touch_gesture: None,
+            stdin_commands: false,
+            build_info: None,
+            retention: retention::CaptureRetention::default(),
+            capture_saved_hooks: Vec::new(),
+            trigger_conditions: Mutex::default(),
+            capture_backend: Mutex::default(),
+            renderdoc_hotkeys: false,
+            focus_toggle_keys: None,
+            unload_crash_handler: false,
+            node_debug_groups: true,
+            label_gpu_resources: false,
+            deterministic_delta: None,
+            frame_step_key: None,
+            strict: false,
+            shader_debug_info: false,
+            wgpu_validation: false,
+            #[cfg(feature = "compress")]
+            compress_captures: None,
+            #[cfg(feature = "remote")]
+            http_port: None,
+        }
+    }
+}
+
+impl RenderDocPlugin {
+    /// Create a new RenderDocPlugin with a specific key code set as the trigger key, instead of the default F10.
+    #[deprecated(
+        since = "0.3.0",
+        note = "use `RenderDocPlugin::builder().trigger_key(..)` instead"
+    )]
+    pub fn new_with_trigger_key(key_code: KeyCode) -> Self {
+        Self::builder().trigger_key(key_code).build()
+    }
+
+    /// Creates a new RenderDocPlugin with no default hook. [RenderDocTrigger] must be used manually to trigger a capture.
+    #[deprecated(
+        since = "0.3.0",
+        note = "use `RenderDocPlugin::builder().no_trigger()` instead"
+    )]
+    pub fn new_without_trigger() -> Self {
+        Self::builder().no_trigger().build()
+    }
+
+    /// The wgpu instance flags enabled with [RenderDocPluginBuilder::shader_debug_info] and
+    /// [RenderDocPluginBuilder::wgpu_validation], to add to the `WgpuSettings` that `RenderPlugin` is created with.
+    pub fn wgpu_instance_flags(&self) -> InstanceFlags {
+        let mut flags = InstanceFlags::empty();
+        flags.set(InstanceFlags::DEBUG, self.shader_debug_info);
+        flags.set(InstanceFlags::VALIDATION, self.wgpu_validation);
+        flags
+    }
+}
+
+impl Plugin for RenderDocPlugin {
+    fn build(&self, app: &mut bevy::app::App) {
+        let settings = FileSettings {
+            binding: self.binding,
+            capture_path: self.capture_path.clone(),
+            overlay: self.overlay.clone(),
+            capture_options: self.capture_options.clone(),
+        };
+        #[cfg(feature = "config")]
+        let settings = match config::ConfigFile::load() {
+            Some(file) => file.apply(settings),
+            None => settings,
+        };
+
+        let overrides = env::EnvOverrides::read();
+        let enabled = self.enabled && !overrides.disabled;
+        let startup_frames = overrides.startup_frames.unwrap_or(self.startup_frames);
+
+        // Requests made while RenderDoc is not attached are simply never handled.
+        let requests = Arc::new(CaptureRequests {
+            max_queued: self.max_queued_captures,
+            frame_offset: self.capture_frame_offset,
+            trigger_cooldown: self.trigger_cooldown,
+            open_replay_ui_always: self.open_replay_ui,
+            build_info: self.build_info.clone(),
+            ..Default::default()
+        });
+        requests.enabled.store(enabled, Ordering::SeqCst);
+
+        app.insert_resource(RenderDocTrigger {
+            requests: requests.clone(),
+        });
+        match settings.overlay {
+            Some(overlay) => app.insert_resource(overlay),
+            None => app.init_resource::<RenderDocOverlaySettings>(),
+        };
+        app.insert_resource(RenderDocSettings {
+            trigger: overrides.trigger.unwrap_or(settings.binding),
+            allow_fullscreen: settings.capture_options.allow_fullscreen,
+            allow_vsync: settings.capture_options.allow_vsync,
+            consume_trigger: self.consume_trigger,
+            require_focus: self.require_focus,
+        });
+        app.register_type::<RenderDocSettings>()
+            .register_type::<TriggerBinding>()
+            .register_type::<KeyModifiers>()
+            .register_type::<RenderDocOverlaySettings>()
+            .register_type::<RenderDocStatus>()
+            .register_type::<CaptureOptions>()
+            .register_type::<CaptureSource>()
+            .register_type::<CaptureOnSpawn>()
+            .register_type::<CaptureThisView>();
+        app.init_resource::<CaptureHistory>();
+        app.add_event::<CaptureSaved>();
+        app.add_event::<RequestCapture>();
+        app.add_event::<CaptureSuppressed>();
+        app.add_event::<CaptureBackendSelected>();
+        app.add_event::<UnsupportedGraphicsBackend>();
+        app.add_event::<PausedAfterCapture>();
+        app.add_event::<CaptureFailed>();
+        #[cfg(feature = "scene")]
+        app.init_resource::<CaptureSceneFilter>();
+        #[cfg(feature = "sidecar")]
+        app.init_resource::<CaptureMetadataSources>();
+
+        // Only the resources apps may access are added without a backend to capture with, so requests are simply
+        // rejected.
+        let capture_path = overrides.capture_path.or(settings.capture_path);
+        let backend = self.capture_backend.lock().unwrap().take();
+        // PIX and RenderDoc aren't attached at the same time, so PIX is used whenever it's attached.
+        #[cfg(all(feature = "pix", windows))]
+        let backend = backend.or_else(|| {
+            let pix = pix::PixBackend::attached(capture_path.clone())?;
+            Some(Box::new(pix) as Box<dyn FrameCaptureBackend>)
+        });
+        // Metal captures are only possible once enabled, e.g. by running the app from Xcode.
+        #[cfg(all(feature = "metal", target_vendor = "apple"))]
+        let backend = backend.or_else(|| {
+            if !MetalCaptureBackend::captures_enabled() {
+                debug!("Metal captures are disabled, not taking captures with Metal");
+                return None;
+            }
+            let metal = match &capture_path {
+                Some(path) => MetalCaptureBackend::new(path),
+                None => MetalCaptureBackend::default(),
+            };
+            Some(Box::new(metal) as Box<dyn FrameCaptureBackend>)
+        });
+        // Failures are reported without RenderDoc too, as that's when automation most needs to know about them.
+        if self.strict {
+            app.insert_resource(StrictCaptures);
+        }
+        app.add_systems(First, send_failed_captures.in_set(RenderDocSystems));
+        // Configured before returning without RenderDoc, so the sets never run rather than running every frame.
+        app.init_resource::<CaptureFrame>()
+            .configure_sets(
+                Last,
+                (
+                    DuringCapture.run_if(|frame: Res<CaptureFrame>| frame.captured),
+                    AfterCapture.run_if(|frame: Res<CaptureFrame>| {
+                        frame.previously_captured && !frame.captured
+                    }),
+                )
+                    .after(predict_captured_frame)
+                    .in_set(RenderDocSystems),
+            )
+            .add_systems(Last, predict_captured_frame.in_set(RenderDocSystems));
+
+        if backend.is_none() && !RENDERDOC_SUPPORTED {
+            if cfg!(any(target_arch = "wasm32", target_vendor = "apple")) {
+                info!("RenderDoc is not supported on this target, not registering capture hook");
+            }
+            app.init_resource::<RenderDocStatus>();
+            return;
+        }
+
+        app.add_observer(capture_on_trigger);
+        app.add_observer(capture_on_spawn);
+        #[cfg(feature = "picking")]
+        app.add_observer(picking::capture_on_click);
+        app.add_systems(
+            Last,
+            (
+                view_scope::skip_other_views.in_set(DuringCapture),
+                view_scope::restore_other_views.in_set(AfterCapture),
+            ),
+        );
+        app.configure_sets(Startup, RenderDocSystems.run_if(not(renderdoc_detached)))
+            .configure_sets(First, RenderDocSystems.run_if(not(renderdoc_detached)))
+            .configure_sets(PreUpdate, RenderDocSystems.run_if(not(renderdoc_detached)))
+            .configure_sets(Update, RenderDocSystems.run_if(not(renderdoc_detached)))
+            .configure_sets(PostUpdate, RenderDocSystems.run_if(not(renderdoc_detached)))
+            .configure_sets(Last, RenderDocSystems.run_if(not(renderdoc_detached)));
+        app.add_systems(
+            First,
+            mark_detached
+                .run_if(renderdoc_detached)
+                .before(RenderDocSystems),
+        );
+
+        let config = ApiConfig {
+            library_path: self.library_path.clone(),
+            capture_options: settings.capture_options,
+            capture_path,
+            build_info: self.build_info.clone(),
+            renderdoc_hotkeys: self.renderdoc_hotkeys,
+            focus_toggle_keys: self.focus_toggle_keys.clone(),
+            unload_crash_handler: self.unload_crash_handler,
+        };
+
+        let backend = match backend {
+            Some(backend) => Ok(backend),
+            None => config.load().map(|renderdoc| {
+                *requests.attached_renderdoc.lock().unwrap() = Some(renderdoc.api());
+                Box::new(renderdoc) as Box<dyn FrameCaptureBackend>
+            }),
+        };
+        match backend {
+            Ok(backend) => {
+                info!(
+                    "Capturing with {}, registering capture hook",
+                    backend.name()
+                );
+
+                if enabled {
+                    requests.request_frames(startup_frames, CaptureSource::Startup);
+                }
+
+                let status = backend_status(&*backend);
+                app.world_mut().send_event(CaptureBackendSelected {
+                    backend: backend.name().to_owned(),
+                });
+                app.insert_resource(status);
+                app.insert_resource(GraphicsBackendSupport(backend.graphics_backends()));
+                requests.attach(backend);
+            }
+            Err(e) => {
+                debug!(
+                    "Renderdoc could not be loaded, not registering capture hook: {}",
+                    e
+                );
+                app.init_resource::<RenderDocStatus>();
+                app.insert_resource(AttachRenderDoc {
+                    config,
+                    retry: self
+                        .attach_retry
+                        .map(|interval| Timer::new(interval, TimerMode::Repeating)),
+                });
+            }
+        }
+
+        if let Some(frame) = capture_frame_and_exit(self.exit_after_capture) {
+            let handle = app
+                .world()
+                .resource::<RenderDocTrigger>()
+                .capture_at_frame(frame);
+            app.insert_resource(ExitAfterCapture { handle });
+            app.add_systems(
+                Update,
+                exit_after_capture
+                    .run_if(resource_exists::<ExitAfterCapture>)
+                    .in_set(RenderDocSystems),
+            );
+        }
+
+        app.add_systems(
+            First,
+            (
+                count_main_frames,
+                attach_renderdoc.run_if(resource_exists::<AttachRenderDoc>),
+                send_saved_captures,
+                send_suppressed_captures,
+                run_capture_saved_hooks.run_if(resource_exists::<CaptureSavedHooks>),
+            )
+                .chain()
+                .in_set(RenderDocSystems),
+        );
+        if !self.capture_saved_hooks.is_empty() {
+            app.insert_resource(CaptureSavedHooks(self.capture_saved_hooks.clone()));
+        }
+        app.add_systems(PostUpdate, handle_capture_requests.in_set(RenderDocSystems));
+        app.add_systems(
+            First,
+            pause_after_capture
+                .run_if(resource_exists::<Time<Virtual>>)
+                .before(TimeSystem)
+                .in_set(RenderDocSystems),
+        );
+        app.add_systems(
+            PostUpdate,
+            apply_present_options
+                .run_if(resource_changed::<RenderDocSettings>)
+                .in_set(RenderDocSystems),
+        );
+        diagnostics::register_diagnostics(app);
+        app.add_systems(
+            Update,
+            diagnostics::diagnostic_system.in_set(RenderDocSystems),
+        );
+        #[cfg(feature = "notify")]
+        app.add_systems(
+            Update,
+            notify::notify_saved_captures.in_set(RenderDocSystems),
+        );
+        #[cfg(feature = "toast")]
+        app.add_systems(
+            Update,
+            (toast::spawn_capture_toasts, toast::despawn_expired_toasts).in_set(RenderDocSystems),
+        );
+        #[cfg(feature = "screenshot")]
+        app.add_systems(
+            Update,
+            screenshot::screenshot_saved_captures.in_set(RenderDocSystems),
+        );
+        if self.retention.max_count.is_some() || self.retention.max_age.is_some() {
+            app.insert_resource(self.retention);
+            // Delete old captures only once this frame's sidecars have been written and compressions have started.
+            let delete_old_captures = retention::delete_old_captures.in_set(RenderDocSystems);
+            #[cfg(feature = "compress")]
+            let delete_old_captures = delete_old_captures.after(compress::compress_saved_captures);
+            app.add_systems(Last, delete_old_captures);
+        }
+        #[cfg(feature = "compress")]
+        if let Some(keep_original) = self.compress_captures {
+            app.insert_resource(compress::CaptureCompression::new(keep_original))
+                .add_event::<CaptureCompressed>()
+                .add_systems(
+                    Last,
+                    compress::compress_saved_captures.in_set(RenderDocSystems),
+                );
+        }
+        #[cfg(feature = "scene")]
+        app.add_systems(
+            Update,
+            scene::write_scene_snapshots.in_set(RenderDocSystems),
+        );
+        #[cfg(feature = "sidecar")]
+        app.add_systems(
+            Update,
+            sidecar::write_capture_sidecars.in_set(RenderDocSystems),
+        );
+        app.add_systems(
+            PostUpdate,
+            fire_scheduled_captures
+                .run_if(resource_exists::<FrameCount>)
+                .in_set(RenderDocSystems),
+        );
+
+        app.add_systems(
+            Update,
+            check_graphics_backend
+                .run_if(resource_exists_and_changed::<GraphicsBackendSupport>)
+                .run_if(resource_exists::<RenderAdapterInfo>)
+                .in_set(RenderDocSystems),
+        );
+
+        if self.validation_error_capture {
+            app.add_systems(
+                Startup,
+                install_error_handler
+                    .run_if(resource_exists::<RenderDevice>)
+                    .in_set(RenderDocSystems),
+            );
+        }
+
+        let periodic = self.periodic_capture.filter(|(_, max)| *max != Some(0));
+        if let Some((interval, max_captures)) = periodic {
+            app.insert_resource(PeriodicCapture {
+                timer: Timer::new(interval, TimerMode::Repeating),
+                remaining: max_captures,
+            });
+            app.add_systems(
+                PostUpdate,
+                periodic_capture
+                    .run_if(resource_exists::<PeriodicCapture>)
+                    .in_set(RenderDocSystems),
+            );
+        }
+
+        if let Some(spike) = self.spike_capture {
+            app.insert_resource(spike);
+            app.add_systems(PostUpdate, spike_capture.in_set(RenderDocSystems));
+        }
+
+        // Runs right after input is read, so a consumed binding is never seen by the rest of the app.
+        app.add_systems(
+            PreUpdate,
+            trigger_on_binding
+                .run_if(resource_exists::<ButtonInput<KeyCode>>)
+                .after(InputSystem)
+                .in_set(RenderDocSystems),
+        );
+        if let Some(key) = self.frame_step_key {
+            app.insert_resource(frame_step::FrameStep::new(key));
+            app.add_systems(
+                PreUpdate,
+                frame_step::read_frame_step_key
+                    .run_if(resource_exists::<ButtonInput<KeyCode>>)
+                    .run_if(resource_exists::<Time<Virtual>>)
+                    .after(InputSystem)
+                    .in_set(RenderDocSystems),
+            );
+            // Time is unpaused for exactly one update, and the capture is stamped with the frame stepped to.
+            app.add_systems(
+                First,
+                (
+                    frame_step::begin_frame_step.before(TimeSystem),
+                    frame_step::end_frame_step
+                        .after(TimeSystem)
+                        .after(count_main_frames),
+                )
+                    .run_if(resource_exists::<Time<Virtual>>)
+                    .in_set(RenderDocSystems),
+            );
+        }
+        for add_condition in std::mem::take(&mut *self.trigger_conditions.lock().unwrap()) {
+            add_condition(app);
+        }
+
+        #[cfg(feature = "signal")]
+        if self.signal_trigger {
+            #[cfg(unix)]
+            signal::install(app);
+            #[cfg(not(unix))]
+            warn!("Signal triggers are only supported on Unix, not installing one");
+        }
+
+        #[cfg(feature = "remote")]
+        if let Some(port) = self.http_port {
+            http::spawn(requests.clone(), port);
+        }
+
+        if let Some(path) = &self.trigger_file {
+            app.insert_resource(file_trigger::FileTrigger::new(path.clone()));
+            app.add_systems(
+                PostUpdate,
+                file_trigger::trigger_on_file.in_set(RenderDocSystems),
+            );
+        }
+
+        app.add_systems(
+            PreUpdate,
+            cancel_on_suspend
+                .run_if(resource_exists::<Events<AppLifecycle>>)
+                .in_set(RenderDocSystems),
+        );
+
+        if let Some(gesture) = self.touch_gesture {
+            app.insert_resource(touch::TouchTrigger::new(gesture));
+            app.add_systems(
+                PreUpdate,
+                touch::trigger_on_touch
+                    .run_if(resource_exists::<Touches>)
+                    .after(InputSystem)
+                    .in_set(RenderDocSystems),
+            );
+        }
+
+        if self.stdin_commands {
+            stdin::spawn(app);
+        }
+    }
+
+    fn finish(&self, app: &mut bevy::app::App) {
+        let requests = &app.world().resource::<RenderDocTrigger>().requests;
+        if !RENDERDOC_SUPPORTED && !requests.available.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let requests = requests.clone();
+        check_wgpu_instance_flags(app, self.wgpu_instance_flags());
+        #[cfg(feature = "brp")]
+        remote::register_methods(app.world_mut());
+
+        // Render world setup is deferred until all plugins are built, so the plugin can be added before `RenderPlugin`.
+        let Some(sub_app) = app.get_sub_app_mut(RenderApp) else {
+            warn!("RenderApp does not exist, not registering capture hook. Is RenderPlugin added?");
+            requests.available.store(false, Ordering::SeqCst);
+            requests.attached.lock().unwrap().take();
+            app.world_mut().resource_mut::<RenderDocStatus>().available = false;
+            app.world_mut().remove_resource::<AttachRenderDoc>();
+            return;
+        };
+
+        sub_app.insert_resource(RenderDocCaptureRequests { requests });
+        sub_app.add_systems(
+            ExtractSchedule,
+            extract_resource::<RenderDocOverlaySettings>,
+        );
+        sub_app.add_systems(
+            ExtractSchedule,
+            frame_marker::extract_frame_marker
+                .after(RenderDocCaptureSet::Start)
+                .run_if(resource_exists::<RenderDocData>),
+        );
+        sub_app.add_systems(
+            ExtractSchedule,
+            view_scope::extract_captured_views
+                .after(RenderDocCaptureSet::Start)
+                .run_if(resource_exists::<RenderDocData>),
+        );
+        sub_app.add_systems(
+            Render,
+            view_scope::add_captured_view_nodes
+                .run_if(run_once)
+                .after(debug_groups::wrap_render_graph_nodes)
+                .in_set(RenderSet::Prepare),
+        );
+        // Added once nodes are wrapped in debug groups, so the marker stays outside of them.
+        sub_app.add_systems(
+            Render,
+            frame_marker::add_frame_marker_node
+                .run_if(run_once)
+                .after(debug_groups::wrap_render_graph_nodes)
+                .in_set(RenderSet::Prepare),
+        );
+        if self.node_debug_groups {
+            sub_app.add_systems(
+                ExtractSchedule,
+                debug_groups::extract_camera_labels
+                    .after(RenderDocCaptureSet::Start)
+                    .run_if(resource_exists::<RenderDocData>),
+            );
+            sub_app.add_systems(
+                Render,
+                debug_groups::wrap_render_graph_nodes
+                    .run_if(run_once)
+                    .in_set(RenderSet::Prepare),
+            );
+        }
+        if self.label_gpu_resources {
+            sub_app.init_resource::<labels::ImageLabels>();
+            sub_app.add_systems(
+                Render,
+                labels::label_extracted_images
+                    .run_if(resource_exists::<ExtractedAssets<GpuImage>>)
+                    .in_set(RenderSet::PrepareAssets)
+                    .before(prepare_assets::<GpuImage>),
+            );
+        }
+
+        if let Some(delta) = self.deterministic_delta {
+            sub_app.insert_resource(deterministic::DeterministicCapture { delta });
+            // Jitter is set while views are managed and read once they're prepared, so it's removed in between.
+            sub_app.add_systems(
+                Render,
+                (
+                    deterministic::fix_captured_time,
+                    deterministic::remove_captured_jitter,
+                )
+                    .run_if(resource_exists::<RenderDocData>)
+                    .after(RenderSet::ManageViews)
+                    .before(RenderSet::Queue),
+            );
+        }
+
+        sub_app.configure_sets(
+            Render,
+            RenderDocCaptureSet::End
+                .after(RenderSet::Render)
+                .before(RenderSet::Cleanup),
+        );
+        sub_app.add_systems(
+            ExtractSchedule,
+            (
+                receive_attached_api.run_if(not(resource_exists::<RenderDocData>)),
+                handle_api_requests.run_if(resource_exists::<RenderDocData>),
+                start_capture
+                    .in_set(RenderDocCaptureSet::Start)
+                    .run_if(resource_exists::<RenderDocData>),
+                mirror_capture_continues.run_if(resource_exists::<RenderDocData>),
+            )
+                .chain(),
+        );
+        sub_app.add_systems(
+            Render,
+            apply_overlay_settings
+                .run_if(resource_exists_and_changed::<RenderDocOverlaySettings>)
+                .run_if(resource_exists::<RenderDocData>)
+                .in_set(RenderSet::PrepareResources),
+        );
+        sub_app.add_systems(
+            Render,
+            (
+                after_render_end_capture.run_if(resource_exists::<RenderDocData>),
+                detach_renderdoc.run_if(resource_exists::<RenderDocData>),
+            )
+                .chain()
+                .in_set(RenderDocCaptureSet::End),
+        );
+    }
+}
+
+/// The status of `backend`, naming it in [RenderDocStatus::backend].
+fn backend_status(backend: &dyn FrameCaptureBackend) -> RenderDocStatus {
+    RenderDocStatus {
+        backend: Some(backend.name().to_owned()),
+        ..backend.status()
+    }
+}
+
+/// Whether RenderDoc can be loaded, which needs the `renderdoc` feature and a platform RenderDoc supports.
+const RENDERDOC_SUPPORTED: bool = cfg!(all(
+    feature = "renderdoc",
+    not(any(target_arch = "wasm32", target_vendor = "apple"))
+));
+
+/// Environment variable overriding the path the RenderDoc library is loaded from.
+const LIBRARY_PATH_VAR: &str = "RENDERDOC_LIB_PATH";
+
+/// Warn if `RenderPlugin` creates the renderer without the instance flags the plugin was configured with.
+fn check_wgpu_instance_flags(app: &App, flags: InstanceFlags) {
+    if flags.is_empty() {
+        return;
+    }
+
+    // Renderers created by the app itself can't be checked.
+    let missing = app
+        .get_added_plugins::<RenderPlugin>()
+        .into_iter()
+        .filter_map(|plugin| match &plugin.render_creation {
+            RenderCreation::Automatic(settings) => Some(flags - settings.instance_flags),
+            RenderCreation::Manual(_) => None,
+        })
+        .find(|missing| !missing.is_empty());
+    if let Some(missing) = missing {
+        warn!(
+            "RenderPlugin was created without the wgpu instance flags {:?}, add RenderDocPlugin::wgpu_instance_flags \
+            to its WgpuSettings to enable them",
+            missing
+        );
+    }
+}
+
+/// Environment variable setting the frame to capture before exiting, see
+/// [RenderDocPluginBuilder::capture_frame_and_exit].
+const CAPTURE_FRAME_VAR: &str = "RENDERDOC_CAPTURE_FRAME";
+
+/// The frame to capture before exiting, taken from [CAPTURE_FRAME_VAR] if set, or from the plugin otherwise.
+fn capture_frame_and_exit(configured: Option<u32>) -> Option<u32> {
+    let Ok(value) = std::env::var(CAPTURE_FRAME_VAR) else {
+        return configured;
+    };
+
+    match value.trim().parse() {
+        Ok(frame) => Some(frame),
+        Err(e) => {
+            warn!(
+                "Ignoring {}={:?}, as it isn't a frame number: {}",
+                CAPTURE_FRAME_VAR, value, e
+            );
+            configured
+        }
+    }
+}
+
+/// The capture to wait for before exiting, for [RenderDocPluginBuilder::capture_frame_and_exit].
+#[derive(Resource)]
+struct ExitAfterCapture {
+    handle: CaptureHandle,
+}
+
+/// Pause the app once captures requested with [RenderDocTrigger::capture_and_pause] have been saved.
+///
+/// Runs before time is updated, so the frame after the capture's is already paused.
+fn pause_after_capture(
+    trigger: Res<RenderDocTrigger>,
+    mut time: ResMut<Time<Virtual>>,
+    pause_state: Option<Res<PauseWithState>>,
+    mut paused: EventWriter<PausedAfterCapture>,
+) {
+    let mut pending = trigger.requests.pause_after.lock().unwrap();
+    if pending.is_empty() {
+        return;
+    }
+
+    let mut saved = None;
+    pending.retain(|handle| match handle.result() {
+        Some(Ok(path)) => {
+            saved = Some(path);
+            false
+        }
+        Some(Err(e)) => {
+            warn!("Capture failed, not pausing: {}", e);
+            false
+        }
+        None => true,
+    });
+    let Some(path) = saved else {
+        return;
+    };
+
+    if pause_state.is_none() {
+        time.pause();
+    }
+    info!("Paused after capturing {}", path.display());
+    paused.write(PausedAfterCapture { path });
+}
+
+/// Exit once the capture has been saved, printing its path to stdout, or with an error if it failed.
+fn exit_after_capture(
+    mut commands: Commands,
+    exit: Res<ExitAfterCapture>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    let Some(result) = exit.handle.result() else {
+        return;
+    };
+
+    match result {
+        Ok(path) => {
+            println!("{}", path.display());
+            app_exit.write(AppExit::Success);
+        }
+        Err(e) => {
+            error!("Capture failed, exiting: {}", e);
+            app_exit.write(AppExit::error());
+        }
+    }
+
+    commands.remove_resource::<ExitAfterCapture>();
+}
+
+/// Settings for loading and configuring the RenderDoc API.
+struct ApiConfig {
+    library_path: Option<PathBuf>,
+    capture_options: CaptureOptions,
+    capture_path: Option<PathBuf>,
+    build_info: Option<String>,
+    renderdoc_hotkeys: bool,
+    focus_toggle_keys: Option<Vec<KeyCode>>,
+    unload_crash_handler: bool,
+}
+
+impl ApiConfig {
+    /// Load the RenderDoc API and apply the configured settings to it.
+    fn load(&self) -> Result<RenderDocBackend, api::Error> {
+        let library_path = std::env::var_os(LIBRARY_PATH_VAR)
+            .map(PathBuf::from)
+            .or_else(|| self.library_path.clone());
+
+        let mut api = api::RenderDocApi::load_with_library(library_path.as_deref())?;
+        api.apply_capture_options(&self.capture_options);
+
+        if self.unload_crash_handler {
+            api.unload_crash_handler();
+        }
+
+        // RenderDoc's own hotkeys would capture alongside the plugin's binding, bypassing its queue and events.
+        if !self.renderdoc_hotkeys {
+            api.disable_hotkeys();
+        }
+
+        if let Some(keys) = &self.focus_toggle_keys {
+            api.bind_focus_toggle_keys(keys);
+        }
+        if let Some(template) = &self.capture_path {
+            api.set_log_file_path_template(template);
+        }
+
+        if let Some(build_info) = &self.build_info {
+            let template = api.get_log_file_path_template().to_path_buf();
+            api.set_log_file_path_template(metadata::with_build_info(&template, build_info));
+        }
+
+        Ok(RenderDocBackend::new(api))
+    }
+}
 
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::{ptr::null, sync::Mutex};
+/// Present while RenderDoc is not attached, holding what's needed to attach it later.
+#[derive(Resource)]
+struct AttachRenderDoc {
+    config: ApiConfig,
+    retry: Option<Timer>,
+}
 
-use bevy::prelude::*;
-use bevy::render::{ExtractSchedule, Render, RenderApp, RenderSet};
+/// Run condition for [RenderDocSystems], which stop running once RenderDoc is detached.
+fn renderdoc_detached(trigger: Res<RenderDocTrigger>) -> bool {
+    trigger.requests.detached.load(Ordering::SeqCst)
+}
+
+/// Reflect [RenderDocTrigger::detach] in [RenderDocStatus], and stop trying to attach RenderDoc.
+fn mark_detached(
+    mut commands: Commands,
+    mut status: ResMut<RenderDocStatus>,
+    trigger: Res<RenderDocTrigger>,
+) {
+    if status.available {
+        status.available = false;
+    }
+    trigger.requests.attached.lock().unwrap().take();
+    commands.remove_resource::<AttachRenderDoc>();
+}
+
+/// Try loading RenderDoc when requested with [RenderDocTrigger::try_attach] or the retry timer finishes.
+fn attach_renderdoc(
+    mut commands: Commands,
+    mut attach: ResMut<AttachRenderDoc>,
+    mut status: ResMut<RenderDocStatus>,
+    mut selected: EventWriter<CaptureBackendSelected>,
+    time: Option<Res<Time<Real>>>,
+    trigger: Res<RenderDocTrigger>,
+) {
+    let requested = trigger
+        .requests
+        .attach_requested
+        .swap(false, Ordering::SeqCst);
+    let retry_due = match (&mut attach.retry, time) {
+        (Some(retry), Some(time)) => retry.tick(time.delta()).just_finished(),
+        _ => false,
+    };
+
+    if !requested && !retry_due {
+        return;
+    }
+
+    let renderdoc = match attach.config.load() {
+        Ok(r) => r,
+        Err(e) => {
+            if requested {
+                debug!("Renderdoc could not be attached: {}", e);
+            }
+            return;
+        }
+    };
+
+    info!("Renderdoc attached, registering capture hook");
 
-type RenderDoc = renderdoc::RenderDoc<renderdoc::V100>;
+    *status = backend_status(&renderdoc);
+    selected.write(CaptureBackendSelected {
+        backend: renderdoc.name().to_owned(),
+    });
+    commands.insert_resource(GraphicsBackendSupport(renderdoc.graphics_backends()));
+    *trigger.requests.attached_renderdoc.lock().unwrap() = Some(renderdoc.api());
+    trigger.requests.attach(Box::new(renderdoc));
+    commands.remove_resource::<AttachRenderDoc>();
+}
 
+/// The graphics backends the attached [FrameCaptureBackend] can capture.
 #[derive(Resource)]
-struct RenderDocData {
-    capture_requested: Arc<AtomicBool>,
-    is_capture_active: bool,
-    api: Mutex<RenderDoc>,
+struct GraphicsBackendSupport(Backends);
+
+/// Warn if the app renders with a graphics backend the attached backend can't capture, once both are known.
+fn check_graphics_backend(
+    support: Res<GraphicsBackendSupport>,
+    adapter: Res<RenderAdapterInfo>,
+    mut status: ResMut<RenderDocStatus>,
+    mut unsupported: EventWriter<UnsupportedGraphicsBackend>,
+) {
+    let graphics_backend = adapter.backend;
+    if support.0.contains(Backends::from(graphics_backend)) {
+        return;
+    }
+
+    let suggestion = match graphics_backend {
+        Backend::Dx12 if cfg!(all(feature = "pix", windows)) => "attach PIX to capture with PIX",
+        Backend::Metal if cfg!(all(feature = "metal", target_vendor = "apple")) => {
+            "run the app from Xcode or with METAL_CAPTURE_ENABLED=1 to capture with Metal"
+        }
+        Backend::Vulkan | Backend::Dx12 | Backend::Gl if RENDERDOC_SUPPORTED => {
+            "launch the app from RenderDoc to capture with RenderDoc"
+        }
+        _ => "set WGPU_BACKEND to render with a graphics backend it supports",
+    };
+    let backend = status.backend.as_deref().unwrap_or("The capture backend");
+    warn!(
+        "{} can't capture {:?}, captures will be empty; {}",
+        backend, graphics_backend, suggestion
+    );
+
+    let graphics_backend = format!("{:?}", graphics_backend);
+    status.unsupported_graphics_backend = Some(graphics_backend.clone());
+    unsupported.write(UnsupportedGraphicsBackend {
+        graphics_backend,
+        suggestion: suggestion.to_owned(),
+    });
 }
 
-/// The RenderDoc capture plugin.
-pub struct RenderDocPlugin {
-    key_code: Option<KeyCode>,
+/// Set up the render world once RenderDoc has been attached.
+fn receive_attached_api(
+    mut commands: Commands,
+    trigger: Extract<Res<RenderDocTrigger>>,
+    overlay: Option<Res<RenderDocOverlaySettings>>,
+) {
+    let requests = &trigger.requests;
+    let Some(mut backend) = requests.attached.lock().unwrap().take() else {
+        return;
+    };
+
+    // The overlay is otherwise only applied when its settings change, which may have happened before attaching.
+    if let Some(overlay) = overlay {
+        backend.set_overlay(&overlay);
+    }
+
+    #[cfg(all(
+        feature = "renderdoc",
+        not(any(target_arch = "wasm32", target_vendor = "apple"))
+    ))]
+    if let Some(api) = requests.attached_renderdoc.lock().unwrap().take() {
+        commands.insert_resource(RenderDocApi { api });
+    }
+
+    commands.insert_resource(RenderDocData {
+        requests: requests.clone(),
+        frames_remaining: 0,
+        is_capture_active: false,
+        is_span_active: false,
+        capture_target: CaptureTarget::default(),
+        frames_in_capture: 0,
+        active_handle: None,
+        active_source: CaptureSource::default(),
+        active_metadata: CaptureMetadata::default(),
+        capture_started: Instant::now(),
+        burst_source: CaptureSource::default(),
+        open_replay_ui_always: requests.open_replay_ui_always,
+        open_replay_ui_pending: false,
+        backend: Mutex::new(backend),
+    });
 }
 
-/// Resource for triggering a renderdoc capture using [RenderDocTrigger::capture].
-///
-/// Triggers are handled during [ExtractSchedule] and you should enable capture prior.
-///
-/// By default, a hook is registered that triggers a renderdoc capture upon pressing F10.
-///
-/// # Example
-/// ```no_run
-/// # use bevy::prelude::*;
-/// # use bevy_renderdoc_capture::*;
-/// pub fn my_system(trigger: Res<RenderDocTrigger>) {
-///
-///     // Do some checks...
-///
-///     trigger.capture();
-/// }
-/// ```
-#[derive(Resource)]
-pub struct RenderDocTrigger {
-    is_capture_active: Arc<AtomicBool>,
+/// Count the main-world frames that have started, which queued captures are stamped with.
+fn count_main_frames(trigger: Res<RenderDocTrigger>) {
+    trigger.requests.main_frame.fetch_add(1, Ordering::SeqCst);
 }
 
-impl RenderDocTrigger {
-    /// Trigger capturing the next frame for RenderDoc.
-    ///
-    /// This operation cannot be undone.
-    pub fn capture(&self) {
-        self.is_capture_active.store(true, Ordering::SeqCst);
+/// Cancel pending captures and the capture in progress when the app is about to be suspended, e.g. when an Android
+/// app goes to the background, as its window is destroyed before another frame is rendered.
+fn cancel_on_suspend(mut events: EventReader<AppLifecycle>, trigger: Res<RenderDocTrigger>) {
+    if events
+        .read()
+        .any(|event| matches!(event, AppLifecycle::WillSuspend))
+    {
+        debug!("App is being suspended, cancelling RenderDoc captures");
+        trigger.cancel();
     }
 }
 
-impl Default for RenderDocPlugin {
-    fn default() -> Self {
-        Self {
-            key_code: Some(KeyCode::F10),
-        }
+/// Trigger a capture when the binding in [RenderDocSettings] is pressed.
+fn trigger_on_binding(
+    settings: Res<RenderDocSettings>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut mouse: Option<ResMut<ButtonInput<MouseButton>>>,
+    mut gamepads: Query<&mut Gamepad>,
+    primary_window: Query<&Window, With<PrimaryWindow>>,
+    trigger: Res<RenderDocTrigger>,
+) {
+    let Some(binding) = settings.trigger else {
+        return;
+    };
+
+    if settings.require_focus && primary_window.single().is_ok_and(|window| !window.focused) {
+        return;
+    }
+
+    if !binding.just_pressed(&keys, mouse.as_deref(), gamepads.iter()) {
+        return;
+    }
+
+    trigger.capture_from(CaptureSource::Binding);
+    if settings.consume_trigger {
+        binding.consume(&mut keys, mouse.as_deref_mut(), &mut gamepads);
     }
 }
 
-impl RenderDocPlugin {
-    /// Create a new RenderDocPlugin with a specific key code set as the trigger key, instead of the default F10.
-    pub fn new_with_trigger_key(key_code: KeyCode) -> Self {
-        Self {
-            key_code: Some(key_code),
+/// Re-apply the vsync and fullscreen options in [RenderDocSettings], alongside any options set with
+/// [RenderDocTrigger::set_capture_options] this frame.
+fn apply_present_options(settings: Res<RenderDocSettings>, trigger: Res<RenderDocTrigger>) {
+    let mut pending = trigger.requests.options.lock().unwrap();
+    let options = pending.get_or_insert_with(CaptureOptions::default);
+
+    if settings.allow_fullscreen.is_some() {
+        options.allow_fullscreen = settings.allow_fullscreen;
+    }
+    if settings.allow_vsync.is_some() {
+        options.allow_vsync = settings.allow_vsync;
+    }
+}
+
+/// Trigger a capture when a condition added with [RenderDocPluginBuilder::trigger_condition] flips from `false` to
+/// `true`.
+fn trigger_on_condition(
+    In(holds): In<bool>,
+    mut held: Local<bool>,
+    trigger: Res<RenderDocTrigger>,
+) {
+    if holds && !*held {
+        trigger.capture_from(CaptureSource::Condition);
+    }
+    *held = holds;
+}
+
+/// Arm a capture when [CaptureOnSpawn] is added to an entity.
+fn capture_on_spawn(
+    event: Trigger<OnAdd, CaptureOnSpawn>,
+    markers: Query<&CaptureOnSpawn>,
+    trigger: Res<RenderDocTrigger>,
+) {
+    let entity = event.target();
+    let Ok(marker) = markers.get(entity) else {
+        return;
+    };
+
+    let configure = |capture: &mut QueuedCapture| capture.entity = Some(entity);
+    if marker.delay_frames == 0 {
+        trigger.queue_capture(CaptureSource::Spawn, configure);
+    } else {
+        trigger.schedule(
+            ScheduledFrame::In(marker.delay_frames),
+            CaptureSource::Spawn,
+            configure,
+        );
+    }
+}
+
+/// Forward triggered [RenderDocCapture] events to [RenderDocTrigger].
+fn capture_on_trigger(event: Trigger<RenderDocCapture>, trigger: Res<RenderDocTrigger>) {
+    let RenderDocCapture { frames, name } = event.event().clone();
+
+    match frames {
+        0 => warn!("Ignoring a RenderDocCapture of 0 frames"),
+        1 => {
+            trigger.queue_capture(CaptureSource::Event, |capture| capture.title = name);
         }
+        _ => trigger.capture_frames_from(frames, CaptureSource::Event),
     }
+}
 
-    /// Creates a new RenderDocPlugin with no default hook. [RenderDocTrigger] must be used manually to trigger a capture.
-    pub fn new_without_trigger() -> Self {
-        Self { key_code: None }
+/// Forward [RequestCapture] events to [RenderDocTrigger].
+fn handle_capture_requests(
+    mut events: EventReader<RequestCapture>,
+    trigger: Res<RenderDocTrigger>,
+) {
+    for _ in events.read() {
+        trigger.capture_from(CaptureSource::Event);
     }
 }
 
-impl Plugin for RenderDocPlugin {
-    fn build(&self, app: &mut bevy::app::App) {
-        let renderdoc = match RenderDoc::new() {
-            Ok(r) => r,
-            Err(e) => {
-                debug!(
-                    "Renderdoc could not be loaded, not registering capture hook: {}",
-                    e
-                );
-                return;
-            }
-        };
+/// Find out whether the frame about to be extracted is captured, for [DuringCapture] and [AfterCapture].
+fn predict_captured_frame(trigger: Res<RenderDocTrigger>, mut frame: ResMut<CaptureFrame>) {
+    frame.previously_captured = frame.captured;
+    frame.captured = trigger.requests.next_frame_captured();
+}
 
-        info!("Renderdoc available, registering capture hook");
+/// Trigger captures scheduled for the current frame.
+fn fire_scheduled_captures(trigger: Res<RenderDocTrigger>, frame_count: Res<FrameCount>) {
+    let mut scheduled = trigger.requests.scheduled.lock().unwrap();
+    if scheduled.is_empty() {
+        return;
+    }
 
-        let sub_app = app.sub_app_mut(RenderApp);
+    for capture in scheduled.iter_mut() {
+        if let ScheduledFrame::In(frames) = capture.frame {
+            capture.frame = ScheduledFrame::At(frame_count.0.wrapping_add(frames));
+        }
+    }
 
-        sub_app.add_systems(ExtractSchedule, start_capture);
-        sub_app.add_systems(
-            Render,
-            after_render_end_capture
-                .after(RenderSet::Render)
-                .before(RenderSet::Cleanup),
+    let (due, pending) = std::mem::take(&mut *scheduled)
+        .into_iter()
+        .partition::<Vec<_>, _>(
+            |capture| matches!(capture.frame, ScheduledFrame::At(frame) if frame <= frame_count.0),
         );
+    *scheduled = pending;
+    drop(scheduled);
+
+    for scheduled in due {
+        trigger.request_capture(scheduled.capture);
+    }
+}
 
-        let is_capture_active = Arc::new(AtomicBool::new(false));
+/// Trigger a capture each time the [PeriodicCapture] timer finishes, until the capture limit is reached.
+fn periodic_capture(
+    mut commands: Commands,
+    mut periodic: ResMut<PeriodicCapture>,
+    time: Res<Time<Real>>,
+    trigger: Res<RenderDocTrigger>,
+) {
+    if !periodic.timer.tick(time.delta()).just_finished() {
+        return;
+    }
 
-        sub_app.insert_resource(RenderDocData {
-            capture_requested: is_capture_active,
-            is_capture_active: false,
-            api: Mutex::new(renderdoc),
-        });
+    trigger.capture_from(CaptureSource::Periodic);
 
-        if let Some(key_code) = self.key_code {
-            app.add_systems(
-                PostUpdate,
-                move |keys: Res<ButtonInput<KeyCode>>, trigger: Res<RenderDocTrigger>| {
-                    if keys.just_pressed(key_code) {
-                        trigger.capture();
-                    }
-                },
-            );
+    if let Some(remaining) = &mut periodic.remaining {
+        *remaining = remaining.saturating_sub(1);
+        if *remaining == 0 {
+            commands.remove_resource::<PeriodicCapture>();
         }
     }
 }
 
-/// Default system for starting a capture, based on a key press.
-fn start_capture(mut renderdoc: ResMut<RenderDocData>) {
-    if renderdoc
-        .capture_requested
-        .fetch_and(false, Ordering::SeqCst)
+/// Trigger a capture after a frame exceeding the [SpikeCapture] threshold, unless still cooling down.
+fn spike_capture(
+    mut spike: ResMut<SpikeCapture>,
+    time: Res<Time<Real>>,
+    trigger: Res<RenderDocTrigger>,
+) {
+    if time.delta() < spike.threshold {
+        return;
+    }
+
+    let now = time.elapsed();
+    if spike
+        .last_capture
+        .is_some_and(|last| now - last < spike.cooldown)
     {
-        renderdoc.is_capture_active = true;
-        let api = renderdoc.api.get_mut().unwrap();
-        api.start_frame_capture(null(), null());
+        return;
+    }
+
+    info!("Frame took {:?}, triggering capture", time.delta());
+    spike.last_capture = Some(now);
+    trigger.capture_from(CaptureSource::Spike);
+}
+
+/// Start the next capture through the backend as the frame is extracted, taking requests in order: a span, the next
+/// frame of a burst, then the oldest queued capture that is due.
+fn start_capture(
+    mut renderdoc: ResMut<RenderDocData>,
+    device: Option<Res<RenderDevice>>,
+    windows: Option<Res<ExtractedWindows>>,
+    adapter: Option<Res<RenderAdapterInfo>>,
+    frame_count: Extract<Option<Res<FrameCount>>>,
+    entities: Extract<&Entities>,
+    names: Extract<Query<&Name>>,
+) {
+    let frame = frame_count.as_deref().map(|frame_count| frame_count.0);
+    let requests = renderdoc.requests.clone();
+    let metadata = || {
+        CaptureMetadata::new(
+            frame,
+            entities.len(),
+            adapter.as_deref(),
+            requests.user_comment.lock().unwrap().clone(),
+            requests.build_info.clone(),
+        )
+    };
+
+    if renderdoc.requests.span_start.swap(false, Ordering::SeqCst) && !renderdoc.is_span_active {
+        renderdoc.is_span_active = true;
+        let target = capture_target(&renderdoc, None, device.as_deref(), windows.as_deref());
+        begin_frame_capture(
+            &mut renderdoc,
+            target,
+            None,
+            CaptureSource::Span,
+            metadata(),
+        );
+    }
+
+    // Frame captures wait until any open span has ended.
+    if renderdoc.is_span_active {
+        return;
+    }
+
+    let requested = renderdoc.requests.frames.swap(0, Ordering::SeqCst);
+    if requested > 0 {
+        let source = *renderdoc.requests.burst_source.lock().unwrap();
+        renderdoc.burst_source = source;
+    }
+    renderdoc.frames_remaining = renderdoc.frames_remaining.max(requested);
+
+    // Bursts capture consecutive frames, so queued captures wait until they're done.
+    if renderdoc.frames_remaining > 0 {
+        renderdoc.frames_remaining -= 1;
+
+        let target = capture_target(&renderdoc, None, device.as_deref(), windows.as_deref());
+        let source = renderdoc.burst_source;
+        begin_frame_capture(&mut renderdoc, target, None, source, metadata());
+        return;
+    }
+
+    // The main world is paused during extraction, so this is the frame being extracted.
+    let extracted_frame = requests.main_frame.load(Ordering::SeqCst);
+    let queued = {
+        let mut queue = requests.queue.lock().unwrap();
+        let due = queue.front().is_some_and(|capture| {
+            capture
+                .requested_frame
+                .saturating_add(requests.frame_offset)
+                <= extracted_frame
+        });
+        if due { queue.pop_front() } else { None }
+    };
+    if let Some(capture) = queued {
+        renderdoc.active_handle = Some(capture.handle);
+        renderdoc.open_replay_ui_pending |= capture.open_replay_ui;
+
+        let mut metadata = metadata();
+        metadata.entity = capture.entity.map(|entity| match names.get(entity) {
+            Ok(name) => format!("{} ({})", entity, name),
+            Err(_) => entity.to_string(),
+        });
+        metadata.picked = capture.picked;
+        metadata.capture_comment = capture.comment;
+
+        let target = capture_target(
+            &renderdoc,
+            capture.window,
+            device.as_deref(),
+            windows.as_deref(),
+        );
+        begin_frame_capture(
+            &mut renderdoc,
+            target,
+            capture.title.as_deref(),
+            capture.source,
+            metadata,
+        );
+    }
+}
+
+/// Tell the main world whether the capture in progress continues into the next frame, see [DuringCapture].
+fn mirror_capture_continues(renderdoc: Res<RenderDocData>) {
+    let continues = renderdoc.is_span_active || renderdoc.frames_remaining > 0;
+    renderdoc
+        .requests
+        .capture_continues
+        .store(continues, Ordering::SeqCst);
+}
+
+/// Find the device and window for the next capture.
+fn capture_target(
+    renderdoc: &RenderDocData,
+    window: Option<Entity>,
+    device: Option<&RenderDevice>,
+    windows: Option<&ExtractedWindows>,
+) -> CaptureTarget {
+    let window = windows.and_then(|windows| {
+        let entity = window
+            .or(*renderdoc.requests.target_window.lock().unwrap())
+            .or(windows.primary)?;
+        windows.get(&entity)
+    });
+
+    // Headless apps have no window to match, so RenderDoc captures whatever is rendered on the device instead.
+    if window.is_none() {
+        debug!("No window to capture, matching the render device only");
+    }
+
+    CaptureTarget {
+        device: device.cloned(),
+        window: window.map(|window| window.handle.get_window_handle()),
+        window_entity: window.map(|window| window.entity),
+    }
+}
+
+/// Apply capture options and open captures requested through [RenderDocTrigger].
+fn handle_api_requests(mut renderdoc: ResMut<RenderDocData>) {
+    let renderdoc = &mut *renderdoc;
+    let options = renderdoc.requests.options.lock().unwrap().take();
+    let open_paths = std::mem::take(&mut *renderdoc.requests.open_paths.lock().unwrap());
+    let backend = renderdoc.backend.get_mut().unwrap();
+
+    if let Some(options) = options {
+        backend.apply_capture_options(&options);
+    }
+
+    for path in open_paths {
+        backend.open_capture(&path);
     }
 }
 
+/// Start a frame capture, applying any title requested for it.
+fn begin_frame_capture(
+    renderdoc: &mut RenderDocData,
+    target: CaptureTarget,
+    title: Option<&str>,
+    source: CaptureSource,
+    metadata: CaptureMetadata,
+) {
+    renderdoc.is_capture_active = true;
+    renderdoc.requests.capturing.store(true, Ordering::SeqCst);
+    renderdoc.capture_target = target;
+    renderdoc.active_source = source;
+    renderdoc.active_metadata = metadata;
+    renderdoc.capture_started = Instant::now();
+
+    let backend = renderdoc.backend.get_mut().unwrap();
+    // Profilers show the span as a zone and the message as a marker, to line the capture up with the CPU profile.
+    #[cfg(feature = "trace")]
+    let _span = info_span!(
+        "renderdoc_capture_start",
+        frame = renderdoc.active_metadata.frame
+    )
+    .entered();
+    #[cfg(feature = "trace")]
+    match renderdoc.active_metadata.frame {
+        Some(frame) => info!("{} capture frame {}", backend.name(), frame),
+        None => info!("{} capture started", backend.name()),
+    }
+
+    backend.start_frame_capture(&renderdoc.capture_target, title);
+}
+
+/// Tear down the render world once RenderDoc is detached and the capture in progress, if any, has been discarded.
+fn detach_renderdoc(mut commands: Commands, mut renderdoc: ResMut<RenderDocData>) {
+    if !renderdoc.requests.detached.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let backend = renderdoc.backend.get_mut().unwrap();
+    backend.set_overlay(&RenderDocOverlaySettings {
+        enabled: false,
+        ..default()
+    });
+    commands.remove_resource::<RenderDocData>();
+    #[cfg(all(
+        feature = "renderdoc",
+        not(any(target_arch = "wasm32", target_vendor = "apple"))
+    ))]
+    commands.remove_resource::<RenderDocApi>();
+    info!("RenderDoc detached");
+}
+
 /// Post-render disable frame capture if it was on.
-fn after_render_end_capture(mut renderdoc: ResMut<RenderDocData>) {
+fn after_render_end_capture(
+    mut renderdoc: ResMut<RenderDocData>,
+    windows: Option<Res<ExtractedWindows>>,
+) {
+    let renderdoc = &mut *renderdoc;
+
+    if renderdoc.is_capture_active {
+        renderdoc.frames_in_capture += 1;
+    }
+
+    let span_end_requested = renderdoc.requests.span_end.swap(false, Ordering::SeqCst);
+    let cancel_requested = renderdoc.requests.cancel.swap(false, Ordering::SeqCst);
+    if cancel_requested {
+        renderdoc.frames_remaining = 0;
+    }
+
+    // Span captures stay open until explicitly ended.
+    if renderdoc.is_span_active {
+        if !span_end_requested && !cancel_requested {
+            return;
+        }
+
+        renderdoc.is_span_active = false;
+    }
+
     // Check if the capture is active, and disable it if it is.
     if !renderdoc.is_capture_active {
         return;
     }
 
     renderdoc.is_capture_active = false;
+    renderdoc.requests.capturing.store(false, Ordering::SeqCst);
+    let frame_count = std::mem::take(&mut renderdoc.frames_in_capture);
+    let backend = renderdoc.backend.get_mut().unwrap();
+    let mut target = renderdoc.capture_target.clone();
+    // The window may have been recreated since the capture started, e.g. when an Android app resumes, in which case
+    // no window ends the capture for whichever window it was started on.
+    if let Some(entity) = target.window_entity {
+        let window = windows.as_deref().and_then(|windows| windows.get(&entity));
+        if window.map(|window| window.handle.get_window_handle()) != target.window {
+            target.window = None;
+        }
+    }
+    #[cfg(feature = "trace")]
+    let _span = info_span!("renderdoc_capture_end", frame_count).entered();
+    let discarded = cancel_requested && backend.discard_frame_capture(&target);
+    let saved = if discarded {
+        None
+    } else {
+        backend.end_frame_capture(&target)
+    };
+
+    let handle = renderdoc.active_handle.take();
+    if cancel_requested {
+        // Without discard support the capture is saved regardless, so remove it instead.
+        if let Some(CaptureFile { path, .. }) = &saved {
+            if let Err(e) = std::fs::remove_file(path) {
+                warn!(
+                    "Failed to delete cancelled capture {}: {}",
+                    path.display(),
+                    e
+                );
+            }
+        }
+
+        info!("RenderDoc capture cancelled");
+        renderdoc.open_replay_ui_pending = false;
+        if let Some(handle) = handle {
+            handle.resolve(Err(CaptureError::Cancelled));
+        }
+        return;
+    }
+
+    let Some(CaptureFile {
+        index,
+        path,
+        timestamp,
+    }) = saved
+    else {
+        renderdoc
+            .requests
+            .fail(renderdoc.active_source, CaptureError::NotSaved);
+        if let Some(handle) = handle {
+            handle.resolve(Err(CaptureError::NotSaved));
+        }
+        return;
+    };
+
+    if let Some(handle) = handle {
+        handle.resolve(Ok(path.clone()));
+    }
+
+    let mut comments = vec![renderdoc.active_metadata.to_comments()];
+    comments.extend(renderdoc.active_metadata.capture_comment.clone());
+    if !backend.set_capture_file_comments(&path, &comments.join("\n\n")) {
+        debug!("The capture backend doesn't support capture comments, not attaching them");
+    }
+
+    // Only open the UI once a burst has finished, rather than for every frame in it.
+    if renderdoc.frames_remaining == 0
+        && (renderdoc.open_replay_ui_always || renderdoc.open_replay_ui_pending)
+    {
+        renderdoc.open_replay_ui_pending = false;
+        open_replay_ui(backend.as_mut(), &path);
+    }
+
+    renderdoc.requests.saved.lock().unwrap().push(CaptureSaved {
+        index,
+        path,
+        frame_count,
+        frame: renderdoc.active_metadata.frame,
+        timestamp,
+        duration: renderdoc.capture_started.elapsed(),
+        window: renderdoc.capture_target.window_entity,
+        source: renderdoc.active_source,
+    });
+}
+
+/// Replace wgpu's uncaptured error handler with one that logs the error and triggers a capture.
+fn install_error_handler(device: Res<RenderDevice>, trigger: Res<RenderDocTrigger>) {
+    let requests = trigger.requests.clone();
+
+    device
+        .wgpu_device()
+        .on_uncaptured_error(Box::new(move |error| {
+            error!("wgpu error: {}", error);
+
+            let trigger = RenderDocTrigger {
+                requests: requests.clone(),
+            };
+            trigger.capture_from_with(CaptureSource::ValidationError, |capture| {
+                capture.comment = Some(error.to_string())
+            });
+        }));
+}
+
+/// Push [RenderDocOverlaySettings] to RenderDoc whenever they change.
+fn apply_overlay_settings(
+    settings: Res<RenderDocOverlaySettings>,
+    mut renderdoc: ResMut<RenderDocData>,
+) {
+    let backend = renderdoc.backend.get_mut().unwrap();
+    backend.set_overlay(&settings);
+}
+
+/// Open the given capture in the backend's replay UI, unless one is already connected.
+fn open_replay_ui(backend: &mut dyn FrameCaptureBackend, path: &Path) {
+    if !backend.replay_ui_connected() {
+        backend.open_capture(path);
+    }
+}
+
+/// Run the [RenderDocPluginBuilder::on_capture_saved] callbacks for each saved capture.
+fn run_capture_saved_hooks(hooks: Res<CaptureSavedHooks>, mut events: EventReader<CaptureSaved>) {
+    for event in events.read() {
+        for hook in &hooks.0 {
+            hook(event);
+        }
+    }
+}
+
+/// Send [CaptureSuppressed] events for captures suppressed since the last frame.
+fn send_suppressed_captures(
+    trigger: Res<RenderDocTrigger>,
+    mut events: EventWriter<CaptureSuppressed>,
+) {
+    let suppressed = std::mem::take(&mut *trigger.requests.suppressed.lock().unwrap());
+    events.write_batch(suppressed);
+}
+
+/// Marks that failed captures panic, see [RenderDocPluginBuilder::strict].
+#[derive(Resource)]
+struct StrictCaptures;
+
+/// Send [CaptureFailed] events for captures that failed since the last frame, or panic in strict mode.
+fn send_failed_captures(
+    trigger: Res<RenderDocTrigger>,
+    strict: Option<Res<StrictCaptures>>,
+    mut events: EventWriter<CaptureFailed>,
+) {
+    let failed = std::mem::take(&mut *trigger.requests.failed.lock().unwrap());
+    if let (Some(failure), Some(_)) = (failed.first(), strict) {
+        panic!(
+            "{:?} capture failed in strict mode: {}",
+            failure.source, failure.reason
+        );
+    }
+    events.write_batch(failed);
+}
+
+/// Log where `capture` was saved, naming its index, frame and Unix time, so logs can be matched to capture files and
+/// bug reports.
+fn log_saved_capture(capture: &CaptureSaved) {
+    let frame = capture.frame.map_or_else(
+        || "unknown frame".to_owned(),
+        |frame| format!("frame {}", frame),
+    );
+    let unix_time = capture
+        .timestamp
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    info!(
+        "Saved capture {} of {} at Unix time {}.{:03} to {}",
+        capture.index,
+        frame,
+        unix_time.as_secs(),
+        unix_time.subsec_millis(),
+        capture.path.display()
+    );
+}
+
+/// Forward captures saved by the render world as [CaptureSaved] events, and record them in [CaptureHistory].
+fn send_saved_captures(
+    trigger: Res<RenderDocTrigger>,
+    mut history: ResMut<CaptureHistory>,
+    mut events: EventWriter<CaptureSaved>,
+) {
+    let saved = std::mem::take(&mut *trigger.requests.saved.lock().unwrap());
+    for capture in &saved {
+        log_saved_capture(capture);
+    }
+    history.captures.extend(saved.iter().cloned());
+    events.write_batch(saved);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Keys with `pressed` held down, and `just_pressed` pressed this frame.
+    fn keys(pressed: &[KeyCode], just_pressed: &[KeyCode]) -> ButtonInput<KeyCode> {
+        let mut keys = ButtonInput::default();
+        for &key in pressed {
+            keys.press(key);
+        }
+        keys.clear();
+        for &key in just_pressed {
+            keys.press(key);
+        }
+        keys
+    }
+
+    fn just_pressed(
+        binding: TriggerBinding,
+        keys: &ButtonInput<KeyCode>,
+        mouse: Option<&ButtonInput<MouseButton>>,
+    ) -> bool {
+        binding.just_pressed(keys, mouse, std::iter::empty())
+    }
+
+    #[test]
+    fn reads_held_modifiers() {
+        let held = keys(
+            &[KeyCode::ControlRight, KeyCode::ShiftLeft, KeyCode::KeyA],
+            &[],
+        );
+        assert_eq!(
+            KeyModifiers::pressed(&held),
+            KeyModifiers {
+                control: true,
+                shift: true,
+                ..default()
+            }
+        );
+
+        let held = keys(&[KeyCode::AltLeft, KeyCode::SuperRight], &[]);
+        assert_eq!(
+            KeyModifiers::pressed(&held),
+            KeyModifiers {
+                alt: true,
+                super_key: true,
+                ..default()
+            }
+        );
+        assert_eq!(
+            KeyModifiers::pressed(&keys(&[], &[])),
+            KeyModifiers::default()
+        );
+    }
+
+    #[test]
+    fn key_binding_triggers_when_just_pressed() {
+        let binding = TriggerBinding::Key(KeyCode::F12);
+
+        assert!(just_pressed(binding, &keys(&[], &[KeyCode::F12]), None));
+        assert!(just_pressed(
+            binding,
+            &keys(&[KeyCode::ControlLeft], &[KeyCode::F12]),
+            None
+        ));
+        // Held since an earlier frame.
+        assert!(!just_pressed(binding, &keys(&[KeyCode::F12], &[]), None));
+        assert!(!just_pressed(binding, &keys(&[], &[KeyCode::F11]), None));
+    }
+
+    #[test]
+    fn chord_requires_exactly_its_modifiers() {
+        let binding = TriggerBinding::Chord {
+            modifiers: KeyModifiers {
+                control: true,
+                ..default()
+            },
+            key: KeyCode::KeyC,
+        };
+
+        assert!(just_pressed(
+            binding,
+            &keys(&[KeyCode::ControlLeft], &[KeyCode::KeyC]),
+            None
+        ));
+        assert!(just_pressed(
+            binding,
+            &keys(&[KeyCode::ControlRight], &[KeyCode::KeyC]),
+            None
+        ));
+        // The modifier is missing.
+        assert!(!just_pressed(binding, &keys(&[], &[KeyCode::KeyC]), None));
+        // An extra modifier is held.
+        let extra = keys(
+            &[KeyCode::ControlLeft, KeyCode::ShiftLeft],
+            &[KeyCode::KeyC],
+        );
+        assert!(!just_pressed(binding, &extra, None));
+    }
 
-    let api = renderdoc.api.get_mut().unwrap();
-    api.end_frame_capture(null(), null());
+    #[test]
+    fn mouse_bindings_read_the_mouse() {
+        let mut mouse = ButtonInput::default();
+        mouse.press(MouseButton::Back);
+        let no_keys = keys(&[], &[]);
+        let control = keys(&[KeyCode::ControlLeft], &[]);
+
+        assert!(just_pressed(
+            TriggerBinding::Mouse(MouseButton::Back),
+            &no_keys,
+            Some(&mouse)
+        ));
+        assert!(!just_pressed(
+            TriggerBinding::Mouse(MouseButton::Forward),
+            &no_keys,
+            Some(&mouse)
+        ));
+        // Without mouse input, mouse bindings never trigger.
+        assert!(!just_pressed(
+            TriggerBinding::Mouse(MouseButton::Back),
+            &no_keys,
+            None
+        ));
+
+        let chord = TriggerBinding::MouseChord {
+            modifiers: KeyModifiers {
+                control: true,
+                ..default()
+            },
+            button: MouseButton::Back,
+        };
+        assert!(just_pressed(chord, &control, Some(&mouse)));
+        assert!(!just_pressed(chord, &no_keys, Some(&mouse)));
+    }
 }
@@ -0,0 +1,194 @@
+//! Metadata about the app recorded in the comments of each capture, for triaging captures long after they were taken.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use bevy::render::renderer::RenderAdapterInfo;
+
+/// Versions of the dependencies this crate is built against. Cargo doesn't expose dependency versions to the crate,
+/// so these follow the requirements in `Cargo.toml`.
+const BEVY_VERSION: &str = "0.16";
+const WGPU_VERSION: &str = "24";
+
+/// Metadata gathered when a capture starts.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CaptureMetadata {
+    /// The [FrameCount](bevy::diagnostic::FrameCount) of the first captured frame.
+    pub(crate) frame: Option<u32>,
+    /// Number of entities in the main world.
+    pub(crate) entity_count: u32,
+    /// Name and backend of the graphics adapter.
+    pub(crate) adapter: Option<String>,
+    /// Comment set with [crate::RenderDocTrigger::set_comment].
+    pub(crate) comment: Option<String>,
+    /// Build information set with [crate::RenderDocPluginBuilder::build_info].
+    pub(crate) build_info: Option<String>,
+    /// The entity that armed the capture and its name, see [crate::CaptureOnSpawn].
+    pub(crate) entity: Option<String>,
+    /// The Ctrl+clicked entity and the asset handles it's drawn with, with the `picking` feature.
+    pub(crate) picked: Option<String>,
+    /// The labels of the cameras with [crate::CaptureThisView].
+    pub(crate) views: Vec<String>,
+    /// A comment attached to this capture only, appended after the metadata, e.g. the validation error that armed it.
+    pub(crate) capture_comment: Option<String>,
+}
+
+impl CaptureMetadata {
+    pub(crate) fn new(
+        frame: Option<u32>,
+        entity_count: u32,
+        adapter: Option<&RenderAdapterInfo>,
+        comment: Option<String>,
+        build_info: Option<String>,
+    ) -> Self {
+        Self {
+            frame,
+            entity_count,
+            adapter: adapter.map(|info| format!("{} ({:?})", info.name, info.backend)),
+            comment,
+            build_info,
+            entity: None,
+            picked: None,
+            views: Vec::new(),
+            capture_comment: None,
+        }
+    }
+
+    /// The metadata formatted as capture comments, one `key: value` line per item.
+    pub(crate) fn to_comments(&self) -> String {
+        let mut comments = format!(
+            "bevy-renderdoc-capture: {}\nBevy: {}\nwgpu: {}\n",
+            env!("CARGO_PKG_VERSION"),
+            BEVY_VERSION,
+            WGPU_VERSION
+        );
+
+        if let Some(build_info) = &self.build_info {
+            let _ = writeln!(comments, "Build: {}", build_info);
+        }
+        if let Some(adapter) = &self.adapter {
+            let _ = writeln!(comments, "Adapter: {}", adapter);
+        }
+        if let Some(frame) = self.frame {
+            let _ = writeln!(comments, "Frame: {}", frame);
+        }
+        let _ = writeln!(comments, "Entities: {}", self.entity_count);
+        if let Some(entity) = &self.entity {
+            let _ = writeln!(comments, "Armed by: {}", entity);
+        }
+        if let Some(picked) = &self.picked {
+            let _ = writeln!(comments, "Picked: {}", picked);
+        }
+        for view in &self.views {
+            let _ = writeln!(comments, "Captured view: {}", view);
+        }
+        if let Some(comment) = &self.comment {
+            let _ = writeln!(comments, "Comment: {}", comment);
+        }
+
+        comments.pop();
+        comments
+    }
+}
+
+/// Append `build_info` to the file name of a capture path template, replacing characters unsuitable for file names.
+pub(crate) fn with_build_info(template: &Path, build_info: &str) -> PathBuf {
+    let build_info: String = build_info
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' => c,
+            _ => '_',
+        })
+        .collect();
+
+    let mut file_name = template.file_name().unwrap_or_default().to_os_string();
+    file_name.push("_");
+    file_name.push(build_info);
+    template.with_file_name(file_name)
+}
+
+/// Build information for [crate::RenderDocPluginBuilder::build_info], taken from the `BEVY_RENDERDOC_BUILD_INFO`
+/// environment variable at compile time, e.g. set by a build script, or the version of the calling crate otherwise.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// # let mut app = App::new();
+/// app.add_plugins(RenderDocPlugin::builder().build_info(build_info!()).build());
+/// ```
+#[macro_export]
+macro_rules! build_info {
+    () => {
+        match option_env!("BEVY_RENDERDOC_BUILD_INFO") {
+            Some(build_info) => build_info,
+            None => env!("CARGO_PKG_VERSION"),
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_sanitised_build_info() {
+        let template = Path::new("captures/renderdoc/MyGame");
+
+        assert_eq!(
+            with_build_info(template, "1.2.3-beta"),
+            Path::new("captures/renderdoc/MyGame_1.2.3-beta")
+        );
+        assert_eq!(
+            with_build_info(template, "main@abc123 (dirty)/x"),
+            Path::new("captures/renderdoc/MyGame_main_abc123__dirty__x")
+        );
+    }
+
+    #[test]
+    fn lists_required_metadata() {
+        let metadata = CaptureMetadata::new(None, 12, None, None, None);
+
+        assert_eq!(
+            metadata.to_comments(),
+            format!(
+                "bevy-renderdoc-capture: {}\nBevy: {}\nwgpu: {}\nEntities: 12",
+                env!("CARGO_PKG_VERSION"),
+                BEVY_VERSION,
+                WGPU_VERSION
+            )
+        );
+    }
+
+    #[test]
+    fn lists_optional_metadata_in_order() {
+        let metadata = CaptureMetadata {
+            frame: Some(7),
+            entity_count: 3,
+            adapter: Some("Test GPU (Vulkan)".to_owned()),
+            comment: Some("boss fight".to_owned()),
+            build_info: Some("1.0.0".to_owned()),
+            entity: Some("42v1 (Boss)".to_owned()),
+            picked: Some("7v1: Mesh3d(boss.glb#Mesh0)".to_owned()),
+            views: vec!["Minimap".to_owned(), "Mirror".to_owned()],
+            capture_comment: Some("attached separately".to_owned()),
+        };
+
+        let comments = metadata.to_comments();
+        let lines: Vec<_> = comments.lines().skip(3).collect();
+        assert_eq!(
+            lines,
+            [
+                "Build: 1.0.0",
+                "Adapter: Test GPU (Vulkan)",
+                "Frame: 7",
+                "Entities: 3",
+                "Armed by: 42v1 (Boss)",
+                "Picked: 7v1: Mesh3d(boss.glb#Mesh0)",
+                "Captured view: Minimap",
+                "Captured view: Mirror",
+                "Comment: boss fight",
+            ]
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! A [FrameCaptureBackend] saving Xcode GPU traces through Metal's capture manager, enabled with the `metal` feature
+//! on Apple platforms.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::log::warn;
+use bevy::render::settings::Backends;
+use metal::{CaptureDescriptor, CaptureManager, MTLCaptureDestination};
+use wgpu::hal::api::Metal;
+
+use crate::{CaptureFile, CaptureTarget, FrameCaptureBackend, RenderDocStatus};
+
+/// Takes captures with `MTLCaptureManager`, saving them as `.gputrace` documents that open in Xcode.
+///
+/// RenderDoc doesn't support Metal, so this backend takes its place on macOS and iOS. It's used automatically when
+/// Metal captures are enabled at startup, or can be set explicitly, e.g. to use it regardless:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// # let mut app = App::new();
+/// app.add_plugins(
+///     RenderDocPlugin::builder()
+///         .capture_backend(MetalCaptureBackend::default())
+///         .build(),
+/// );
+/// ```
+///
+/// Metal only allows captures when the app is run from Xcode, with the `METAL_CAPTURE_ENABLED=1` environment variable
+/// set, or with `MetalCaptureEnabled` set in its `Info.plist`. Captures are named after a path template like
+/// RenderDoc's, e.g. `/tmp/my_game` saves `/tmp/my_game_1700000000_0.gputrace`.
+pub struct MetalCaptureBackend {
+    path_template: PathBuf,
+    /// Unix time the backend was created at, keeping capture names unique across runs.
+    session: u64,
+    captures: u32,
+    /// The capture in progress, if Metal started it.
+    active: Option<PathBuf>,
+}
+
+impl MetalCaptureBackend {
+    /// A backend saving captures named after `path_template`.
+    pub fn new(path_template: impl Into<PathBuf>) -> Self {
+        let session = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+
+        Self {
+            path_template: path_template.into(),
+            session,
+            captures: 0,
+            active: None,
+        }
+    }
+
+    /// Whether Metal allows captures, i.e. whether the app was run from Xcode or with captures enabled.
+    pub fn captures_enabled() -> bool {
+        CaptureManager::shared().supports_destination(MTLCaptureDestination::GpuTraceDocument)
+    }
+
+    fn capture_path(&self) -> PathBuf {
+        let name = self
+            .path_template
+            .file_name()
+            .map_or("bevy_capture".into(), |name| name.to_string_lossy());
+        self.path_template.with_file_name(format!(
+            "{}_{}_{}.gputrace",
+            name, self.session, self.captures
+        ))
+    }
+}
+
+impl Default for MetalCaptureBackend {
+    /// Save captures in the temporary directory, like RenderDoc does.
+    fn default() -> Self {
+        Self::new(std::env::temp_dir().join("bevy_capture"))
+    }
+}
+
+/// The Metal device behind `target`, or the system's default device if it can't be determined.
+fn metal_device(target: &CaptureTarget) -> Option<metal::Device> {
+    let device = target.device.as_ref().and_then(|device| {
+        // SAFETY: The device is only cloned, which retains it beyond the callback.
+        unsafe {
+            device.wgpu_device().as_hal::<Metal, _, _>(|device| {
+                device.map(|device| device.raw_device().lock().clone())
+            })
+        }
+    });
+
+    device.or_else(metal::Device::system_default)
+}
+
+impl FrameCaptureBackend for MetalCaptureBackend {
+    fn start_frame_capture(&mut self, target: &CaptureTarget, _title: Option<&str>) {
+        let Some(device) = metal_device(target) else {
+            warn!("No Metal device to capture");
+            return;
+        };
+
+        let manager = CaptureManager::shared();
+        if !Self::captures_enabled() {
+            warn!(
+                "Metal captures are disabled, run the app from Xcode or set METAL_CAPTURE_ENABLED=1 to enable them"
+            );
+            return;
+        }
+
+        let path = self.capture_path();
+        let descriptor = CaptureDescriptor::new();
+        descriptor.set_capture_device(&device);
+        descriptor.set_destination(MTLCaptureDestination::GpuTraceDocument);
+        descriptor.set_output_url(&path);
+
+        match manager.start_capture(&descriptor) {
+            Ok(()) => self.active = Some(path),
+            Err(e) => warn!("Failed to start the Metal capture: {}", e),
+        }
+    }
+
+    fn end_frame_capture(&mut self, _target: &CaptureTarget) -> Option<CaptureFile> {
+        let path = self.active.take()?;
+        CaptureManager::shared().stop_capture();
+
+        let index = self.captures;
+        self.captures += 1;
+        Some(CaptureFile {
+            index,
+            path,
+            timestamp: SystemTime::now(),
+        })
+    }
+
+    fn discard_frame_capture(&mut self, target: &CaptureTarget) -> bool {
+        // GPU traces are bundle directories, which the plugin couldn't delete as it does capture files.
+        if let Some(capture) = self.end_frame_capture(target) {
+            if let Err(e) = std::fs::remove_dir_all(&capture.path) {
+                warn!(
+                    "Failed to delete cancelled capture {}: {}",
+                    capture.path.display(),
+                    e
+                );
+            }
+        }
+        true
+    }
+
+    fn status(&self) -> RenderDocStatus {
+        RenderDocStatus {
+            available: true,
+            api_version: None,
+            library_path: None,
+            capture_path_template: Some(self.path_template.clone()),
+            ..Default::default()
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Metal"
+    }
+
+    fn graphics_backends(&self) -> Backends {
+        Backends::METAL
+    }
+
+    fn open_capture(&mut self, path: &Path) {
+        if let Err(e) = std::process::Command::new("open").arg(path).spawn() {
+            warn!("Failed to open {} in Xcode: {}", path.display(), e);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! A [FrameCaptureBackend] recording the captures it's asked to take without capturing anything, for tests.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use bevy::ecs::entity::Entity;
+
+use crate::{CaptureFile, CaptureTarget, FrameCaptureBackend, RenderDocStatus};
+
+/// A capture taken by a [MockCaptureBackend].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockCapture {
+    /// Index of the capture within the backend's session.
+    pub index: u32,
+    /// Path the capture was reported as saved to, although no file is written.
+    pub path: PathBuf,
+    /// Title requested for the capture, see [crate::RenderDocTrigger::capture_named].
+    pub title: Option<String>,
+    /// The window the capture was taken from.
+    pub window: Option<Entity>,
+    /// Comments attached to the capture, such as its metadata.
+    pub comments: Option<String>,
+}
+
+/// Shared list of the captures a [MockCaptureBackend] has taken, which stays readable once the backend has been
+/// handed to the plugin.
+#[derive(Debug, Clone, Default)]
+pub struct MockCaptureLog(Arc<Mutex<Vec<MockCapture>>>);
+
+impl MockCaptureLog {
+    /// The captures taken so far, oldest first.
+    pub fn captures(&self) -> Vec<MockCapture> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Number of captures taken so far.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    /// Whether no capture was taken yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Takes no captures, but reports each one as saved and records it in a [MockCaptureLog], so apps can test how they
+/// trigger and handle captures without RenderDoc installed.
+///
+/// Captures are still started and ended by the render world, so this needs a renderer. Headless apps without one are
+/// tested with [crate::test::RenderDocTestPlugin] instead, which takes its mock captures in the main world.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// let backend = MockCaptureBackend::default();
+/// let log = backend.log();
+///
+/// # let mut app = App::new();
+/// app.add_plugins(RenderDocPlugin::builder().capture_backend(backend).build());
+/// // Once the app has rendered the frames it captures, `log.captures()` lists each capture it took.
+/// ```
+#[derive(Default)]
+pub struct MockCaptureBackend {
+    log: MockCaptureLog,
+    /// The capture in progress, if any.
+    active: Option<MockCapture>,
+}
+
+impl MockCaptureBackend {
+    /// The log the backend records its captures in.
+    pub fn log(&self) -> MockCaptureLog {
+        self.log.clone()
+    }
+}
+
+impl FrameCaptureBackend for MockCaptureBackend {
+    fn start_frame_capture(&mut self, target: &CaptureTarget, title: Option<&str>) {
+        // Indices only count saved captures, like RenderDoc's do.
+        let index = self.log.len() as u32;
+        self.active = Some(MockCapture {
+            index,
+            path: PathBuf::from(format!("mock_capture_{}.rdc", index)),
+            title: title.map(str::to_owned),
+            window: target.window_entity,
+            comments: None,
+        });
+    }
+
+    fn end_frame_capture(&mut self, _target: &CaptureTarget) -> Option<CaptureFile> {
+        let capture = self.active.take()?;
+        let file = CaptureFile {
+            index: capture.index,
+            path: capture.path.clone(),
+            timestamp: SystemTime::now(),
+        };
+        self.log.0.lock().unwrap().push(capture);
+        Some(file)
+    }
+
+    fn discard_frame_capture(&mut self, _target: &CaptureTarget) -> bool {
+        self.active = None;
+        true
+    }
+
+    fn status(&self) -> RenderDocStatus {
+        RenderDocStatus {
+            available: true,
+            ..Default::default()
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Mock"
+    }
+
+    fn set_capture_file_comments(&mut self, path: &Path, comments: &str) -> bool {
+        let mut captures = self.log.0.lock().unwrap();
+        let capture = captures
+            .iter_mut()
+            .rev()
+            .find(|capture| capture.path == path);
+        if let Some(capture) = capture {
+            capture.comments = Some(comments.to_owned());
+        }
+        true
+    }
+
+    fn open_capture(&mut self, _path: &Path) {}
+}
@@ -0,0 +1,24 @@
+//! Desktop notifications for saved captures, enabled with the `notify` feature.
+
+use bevy::prelude::*;
+use notify_rust::Notification;
+
+use crate::CaptureSaved;
+
+/// Show a desktop notification for each saved capture, so they're noticed even from a fullscreen app.
+pub(crate) fn notify_saved_captures(mut events: EventReader<CaptureSaved>) {
+    for event in events.read() {
+        let body = format!("RenderDoc capture saved to {}", event.path.display());
+
+        // Showing a notification can block on the notification daemon, so keep it off the main thread.
+        std::thread::spawn(move || {
+            if let Err(e) = Notification::new()
+                .summary("RenderDoc capture saved")
+                .body(&body)
+                .show()
+            {
+                warn!("Failed to show capture notification: {}", e);
+            }
+        });
+    }
+}
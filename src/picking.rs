@@ -0,0 +1,94 @@
+//! Capturing the next frame when an entity is Ctrl+clicked, enabled with the `picking` feature.
+
+use bevy::asset::ReflectHandle;
+use bevy::picking::events::{Click, Pointer};
+use bevy::picking::pointer::PointerButton;
+use bevy::prelude::*;
+use bevy::reflect::ReflectRef;
+
+use crate::{CaptureSource, RenderDocTrigger};
+
+/// Capture the next frame when an entity is clicked with the primary button while Ctrl is held, recording what it's
+/// drawn with in the capture's comments.
+pub(crate) fn capture_on_click(
+    event: Trigger<Pointer<Click>>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    mut commands: Commands,
+) {
+    let click = event.event();
+    // The click bubbles up to the clicked entity's ancestors, which aren't what the user pointed at.
+    if event.target() != click.target || click.event.button != PointerButton::Primary {
+        return;
+    }
+    let ctrl =
+        keys.is_some_and(|keys| keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]));
+    if !ctrl {
+        return;
+    }
+
+    let entity = click.target;
+    commands.queue(move |world: &mut World| {
+        let picked = match world.get::<Name>(entity) {
+            Some(name) => format!("{} ({}): {}", entity, name, describe_handles(world, entity)),
+            None => format!("{}: {}", entity, describe_handles(world, entity)),
+        };
+        world
+            .resource::<RenderDocTrigger>()
+            .queue_capture(CaptureSource::Picking, |capture| {
+                capture.picked = Some(picked)
+            });
+    });
+}
+
+/// List the asset handles held by `entity`'s components, such as its mesh and material, with their asset paths if
+/// they were loaded from files, e.g. `Mesh3d(models/ship.glb#Mesh0/Primitive0)`.
+fn describe_handles(world: &World, entity: Entity) -> String {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let asset_server = world.get_resource::<AssetServer>();
+    let Ok(components) = world.inspect_entity(entity) else {
+        return "despawned".to_owned();
+    };
+
+    let mut handles = Vec::new();
+    for info in components {
+        let Some(registration) = info.type_id().and_then(|type_id| registry.get(type_id)) else {
+            continue;
+        };
+        let Some(reflected) = registration
+            .data::<ReflectComponent>()
+            .and_then(|component| component.reflect(world.entity(entity)))
+        else {
+            continue;
+        };
+
+        // Handle components such as `Mesh3d` and `MeshMaterial3d` wrap a single handle.
+        let ReflectRef::TupleStruct(tuple) = reflected.reflect_ref() else {
+            continue;
+        };
+        let Some(field) = tuple.field(0).and_then(|field| field.try_as_reflect()) else {
+            continue;
+        };
+        let Some(handle) = registry
+            .get_type_data::<ReflectHandle>(field.as_any().type_id())
+            .and_then(|reflect| reflect.downcast_handle_untyped(field.as_any()))
+        else {
+            continue;
+        };
+
+        let asset = match asset_server.and_then(|server| server.get_path(handle.id())) {
+            Some(path) => path.to_string(),
+            None => format!("{:?}", handle.id()),
+        };
+        handles.push(format!(
+            "{}({})",
+            registration.type_info().type_path_table().short_path(),
+            asset
+        ));
+    }
+
+    if handles.is_empty() {
+        "no asset handles".to_owned()
+    } else {
+        handles.join(", ")
+    }
+}
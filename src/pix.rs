@@ -0,0 +1,204 @@
+//! A [FrameCaptureBackend] taking GPU captures with PIX on Windows through WinPixEventRuntime's programmatic capture
+//! API, enabled with the `pix` feature.
+
+use std::os::windows::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::log::warn;
+use bevy::render::settings::Backends;
+use libloading::Library;
+use wgpu::hal::api::Dx12;
+
+use crate::{CaptureFile, CaptureTarget, FrameCaptureBackend, RenderDocStatus};
+
+/// `PIX_CAPTURE_GPU`, taking a GPU capture rather than a timing capture.
+const PIX_CAPTURE_GPU: u32 = 1 << 1;
+/// `E_PENDING`, returned while PIX is still writing the capture.
+const E_PENDING: i32 = 0x8000_000A_u32 as i32;
+
+/// `PIXCaptureParameters`, a union whose GPU capture variant only holds the file name. It's padded to the size of
+/// the larger timing capture variant, whose fields are left zeroed.
+#[repr(C)]
+struct CaptureParameters {
+    file_name: *const u16,
+    _timing: [u32; 13],
+}
+
+type BeginCapture = unsafe extern "system" fn(u32, *const CaptureParameters) -> i32;
+type EndCapture = unsafe extern "system" fn(i32) -> i32;
+
+/// Takes GPU captures with PIX, saving them as `.wpix` files.
+pub(crate) struct PixBackend {
+    begin_capture: BeginCapture,
+    end_capture: EndCapture,
+    /// Keeps the entry points above loaded.
+    _library: Library,
+    path_template: PathBuf,
+    /// Unix time the backend was created at, keeping capture names unique across runs.
+    session: u64,
+    captures: u32,
+    /// The capture in progress, if PIX started it.
+    active: Option<PathBuf>,
+}
+
+impl PixBackend {
+    /// Load the PIX capture API if PIX is attached to the app, saving captures named after `path_template`.
+    pub(crate) fn attached(path_template: Option<PathBuf>) -> Option<Self> {
+        // PIX injects its GPU capturer when it launches or attaches to the app.
+        let capturer: Vec<u16> = std::ffi::OsStr::new("WinPixGpuCapturer.dll")
+            .encode_wide()
+            .chain(Some(0))
+            .collect();
+        // SAFETY: The name is nul-terminated, and the module handle is only checked for null.
+        let module = unsafe { winapi::um::libloaderapi::GetModuleHandleW(capturer.as_ptr()) };
+        if module.is_null() {
+            return None;
+        }
+
+        // SAFETY: WinPixEventRuntime has no initialization side effects.
+        let library = match unsafe { Library::new("WinPixEventRuntime.dll") } {
+            Ok(library) => library,
+            Err(e) => {
+                warn!(
+                    "PIX is attached, but WinPixEventRuntime.dll could not be loaded: {}",
+                    e
+                );
+                return None;
+            }
+        };
+
+        // SAFETY: The signatures match those declared in `pix3.h`.
+        let (begin_capture, end_capture) = unsafe {
+            let begin = library.get::<BeginCapture>(b"PIXBeginCapture2\0").ok()?;
+            let end = library.get::<EndCapture>(b"PIXEndCapture\0").ok()?;
+            (*begin, *end)
+        };
+
+        let session = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+
+        Some(Self {
+            begin_capture,
+            end_capture,
+            _library: library,
+            path_template: path_template
+                .unwrap_or_else(|| std::env::temp_dir().join("bevy_capture")),
+            session,
+            captures: 0,
+            active: None,
+        })
+    }
+
+    fn capture_path(&self) -> PathBuf {
+        let name = self
+            .path_template
+            .file_name()
+            .map_or("bevy_capture".into(), |name| name.to_string_lossy());
+        self.path_template
+            .with_file_name(format!("{}_{}_{}.wpix", name, self.session, self.captures))
+    }
+
+    /// End the capture in progress, waiting for PIX to finish writing it.
+    fn end(&mut self, discard: bool) -> Option<PathBuf> {
+        let path = self.active.take()?;
+
+        let result = loop {
+            // SAFETY: A capture was started by `PIXBeginCapture2`.
+            match unsafe { (self.end_capture)(discard.into()) } {
+                E_PENDING => std::thread::yield_now(),
+                result => break result,
+            }
+        };
+        if result < 0 {
+            warn!("Failed to end the PIX capture: HRESULT {:#010x}", result);
+            return None;
+        }
+
+        Some(path)
+    }
+}
+
+/// Whether `target` is rendered with DX12, the only backend PIX captures.
+fn is_dx12(target: &CaptureTarget) -> bool {
+    target.device.as_ref().is_some_and(|device| {
+        // SAFETY: The device is only checked for being a DX12 device.
+        unsafe {
+            device
+                .wgpu_device()
+                .as_hal::<Dx12, _, _>(|device| device.is_some())
+        }
+    })
+}
+
+impl FrameCaptureBackend for PixBackend {
+    fn start_frame_capture(&mut self, target: &CaptureTarget, _title: Option<&str>) {
+        if !is_dx12(target) {
+            warn!("PIX only captures DX12, not starting the capture");
+            return;
+        }
+
+        let path = self.capture_path();
+        let file_name: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+        let parameters = CaptureParameters {
+            file_name: file_name.as_ptr(),
+            _timing: [0; 13],
+        };
+
+        // SAFETY: The parameters and the file name they point to outlive the call.
+        let result = unsafe { (self.begin_capture)(PIX_CAPTURE_GPU, &parameters) };
+        if result < 0 {
+            warn!("Failed to start the PIX capture: HRESULT {:#010x}", result);
+            return;
+        }
+
+        self.active = Some(path);
+    }
+
+    fn end_frame_capture(&mut self, _target: &CaptureTarget) -> Option<CaptureFile> {
+        let path = self.end(false)?;
+
+        let index = self.captures;
+        self.captures += 1;
+        Some(CaptureFile {
+            index,
+            path,
+            timestamp: SystemTime::now(),
+        })
+    }
+
+    fn discard_frame_capture(&mut self, _target: &CaptureTarget) -> bool {
+        self.end(true);
+        true
+    }
+
+    fn status(&self) -> RenderDocStatus {
+        RenderDocStatus {
+            available: true,
+            api_version: None,
+            library_path: None,
+            capture_path_template: Some(self.path_template.clone()),
+            ..Default::default()
+        }
+    }
+
+    fn name(&self) -> &str {
+        "PIX"
+    }
+
+    fn graphics_backends(&self) -> Backends {
+        Backends::DX12
+    }
+
+    fn open_capture(&mut self, path: &Path) {
+        // `start` opens the capture with whichever app `.wpix` files are associated with, PIX once installed.
+        let opened = std::process::Command::new("cmd")
+            .args(["/C", "start", ""])
+            .arg(path)
+            .spawn();
+        if let Err(e) = opened {
+            warn!("Failed to open {} in PIX: {}", path.display(), e);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Bevy Remote Protocol methods for taking captures, enabled with the `brp` feature.
+
+use bevy::prelude::*;
+use bevy::remote::{BrpError, BrpResult, RemoteMethodSystemId, RemoteMethods, error_codes};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{CaptureHistory, RenderDocStatus, RenderDocTrigger};
+
+/// Method triggering a capture, see [capture].
+const CAPTURE_METHOD: &str = "bevy_renderdoc/capture";
+/// Method reporting RenderDoc's status and saved captures, see [status].
+const STATUS_METHOD: &str = "bevy_renderdoc/status";
+
+/// Register the RenderDoc methods, if `RemotePlugin` has been added.
+pub(crate) fn register_methods(world: &mut World) {
+    if !world.contains_resource::<RemoteMethods>() {
+        return;
+    }
+
+    let capture = world.register_system(capture);
+    let status = world.register_system(status);
+
+    let mut methods = world.resource_mut::<RemoteMethods>();
+    methods.insert(CAPTURE_METHOD, RemoteMethodSystemId::Instant(capture));
+    methods.insert(STATUS_METHOD, RemoteMethodSystemId::Instant(status));
+}
+
+/// Parameters of [CAPTURE_METHOD], all optional.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct CaptureParams {
+    /// Capture this many consecutive frames instead of one. Takes precedence over `title`.
+    frames: Option<u32>,
+    /// Title of the capture.
+    title: Option<String>,
+}
+
+/// Trigger a capture, returning the number of captures queued ahead of it.
+fn capture(In(params): In<Option<Value>>, trigger: Res<RenderDocTrigger>) -> BrpResult {
+    let params: CaptureParams = match params {
+        Some(params) => serde_json::from_value(params).map_err(|e| BrpError {
+            code: error_codes::INVALID_PARAMS,
+            message: e.to_string(),
+            data: None,
+        })?,
+        None => CaptureParams::default(),
+    };
+
+    if let Some(error) = trigger.rejection() {
+        return Err(BrpError::internal(error));
+    }
+
+    let queued = trigger.queued_captures();
+    if let Some(frames) = params.frames {
+        trigger.capture_frames(frames);
+    } else if let Some(title) = params.title {
+        trigger.capture_named(title);
+    } else {
+        trigger.capture();
+    }
+
+    Ok(json!({ "queued_ahead": queued }))
+}
+
+/// Report whether RenderDoc is attached, along with the captures saved this session.
+fn status(
+    In(_): In<Option<Value>>,
+    trigger: Res<RenderDocTrigger>,
+    status: Res<RenderDocStatus>,
+    history: Res<CaptureHistory>,
+) -> BrpResult {
+    let captures: Vec<Value> = history
+        .captures()
+        .iter()
+        .map(|capture| {
+            json!({
+                "index": capture.index,
+                "path": capture.path,
+                "frame_count": capture.frame_count,
+                "frame": capture.frame,
+                "source": format!("{:?}", capture.source),
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "available": status.available,
+        "backend": status.backend,
+        "unsupported_graphics_backend": status.unsupported_graphics_backend,
+        "enabled": trigger.is_enabled(),
+        "api_version": status.api_version,
+        "library_path": status.library_path,
+        "capture_path_template": status.capture_path_template,
+        "queued_captures": trigger.queued_captures(),
+        "captures": captures,
+    }))
+}
@@ -0,0 +1,170 @@
+//! Automatic deletion of old captures, see [crate::RenderDocPluginBuilder::keep_recent_captures].
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use bevy::prelude::*;
+
+use crate::{CaptureSaved, RenderDocStatus};
+
+/// Extensions of the files saved next to a capture, deleted along with it.
+const SIDECAR_EXTENSIONS: [&str; 4] = ["json", "png", "scn.ron", "rdc.gz"];
+
+/// How many captures to keep, and for how long.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub(crate) struct CaptureRetention {
+    pub(crate) max_count: Option<usize>,
+    pub(crate) max_age: Option<Duration>,
+}
+
+/// Delete old captures made with the capture path template whenever a new capture is saved.
+pub(crate) fn delete_old_captures(
+    mut events: EventReader<CaptureSaved>,
+    retention: Res<CaptureRetention>,
+    status: Res<RenderDocStatus>,
+    #[cfg(feature = "compress")] compression: Option<Res<crate::compress::CaptureCompression>>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+
+    let Some(template) = &status.capture_path_template else {
+        return;
+    };
+
+    let mut captures = match template_captures(template) {
+        Ok(captures) => captures,
+        Err(e) => {
+            warn!(
+                "Failed to list captures made with {}: {}",
+                template.display(),
+                e
+            );
+            return;
+        }
+    };
+
+    // Newest first, so the captures to keep come first.
+    captures.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    let now = SystemTime::now();
+    let keep = retention.max_count.unwrap_or(usize::MAX).max(1);
+    for (index, (path, modified)) in captures.into_iter().enumerate() {
+        let too_old = retention
+            .max_age
+            .is_some_and(|max_age| now.duration_since(modified).is_ok_and(|age| age > max_age));
+        // Captures still being compressed are deleted once they're done, by the next capture's cleanup.
+        #[cfg(feature = "compress")]
+        if compression
+            .as_ref()
+            .is_some_and(|compression| compression.is_compressing(&path))
+        {
+            continue;
+        }
+        if index >= keep || too_old {
+            delete_capture(&path);
+        }
+    }
+}
+
+/// The captures saved with `template`, along with when they were last modified.
+///
+/// Compressed `.rdc.gz` captures are listed by the path of their `.rdc` file, whether or not it was kept.
+fn template_captures(template: &Path) -> std::io::Result<Vec<(PathBuf, SystemTime)>> {
+    let prefix = template.file_name().unwrap_or_default().to_string_lossy();
+    let dir = match template.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let mut captures = HashMap::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+
+        if !name.starts_with(&*prefix) {
+            continue;
+        }
+        let capture = if name.ends_with(".rdc") {
+            path
+        } else if name.ends_with(".rdc.gz") {
+            path.with_extension("")
+        } else {
+            continue;
+        };
+
+        // A capture kept alongside its compressed copy is as old as the newer of the two.
+        let modified = entry.metadata()?.modified()?;
+        let newest = captures.entry(capture).or_insert(modified);
+        *newest = (*newest).max(modified);
+    }
+
+    Ok(captures.into_iter().collect())
+}
+
+/// Delete a capture and the files saved next to it.
+fn delete_capture(path: &Path) {
+    info!("Deleting old capture {}", path.display());
+    // Compressed captures may not have kept their `.rdc` file.
+    if path.exists() {
+        if let Err(e) = fs::remove_file(path) {
+            warn!("Failed to delete capture {}: {}", path.display(), e);
+            return;
+        }
+    }
+
+    for extension in SIDECAR_EXTENSIONS {
+        let sidecar = path.with_extension(extension);
+        if sidecar.exists() {
+            if let Err(e) = fs::remove_file(&sidecar) {
+                warn!("Failed to delete {}: {}", sidecar.display(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_compressed_captures_by_their_rdc_path() {
+        let dir = std::env::temp_dir().join(format!("renderdoc_retention_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for file in [
+            "capture_0.rdc",
+            "capture_1.rdc.gz",
+            "capture_1.json",
+            "capture_2.rdc",
+            "capture_2.rdc.gz",
+            "other_0.rdc",
+        ] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+
+        let mut captures: Vec<_> = template_captures(&dir.join("capture"))
+            .unwrap()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        captures.sort();
+        assert_eq!(
+            captures,
+            vec![
+                dir.join("capture_0.rdc"),
+                dir.join("capture_1.rdc"),
+                dir.join("capture_2.rdc"),
+            ]
+        );
+
+        delete_capture(&dir.join("capture_1.rdc"));
+        assert!(!dir.join("capture_1.rdc.gz").exists());
+        assert!(!dir.join("capture_1.json").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
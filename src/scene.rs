@@ -0,0 +1,64 @@
+//! Scene snapshots saved alongside captures, enabled with the `scene` feature.
+
+use std::fs;
+
+use bevy::prelude::*;
+use bevy::scene::SceneFilter;
+
+use crate::CaptureSaved;
+
+/// Resource choosing which components and resources are included in the scene snapshot saved with each capture.
+///
+/// By default every reflected component is included, but no resources, as many resources can't be serialized.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// pub fn snapshot_transforms_only(mut filter: ResMut<CaptureSceneFilter>) {
+///     filter.components = SceneFilter::deny_all().allow::<Transform>();
+/// }
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct CaptureSceneFilter {
+    /// Components included in the snapshot.
+    pub components: SceneFilter,
+    /// Resources included in the snapshot.
+    pub resources: SceneFilter,
+}
+
+impl Default for CaptureSceneFilter {
+    fn default() -> Self {
+        Self {
+            components: SceneFilter::allow_all(),
+            resources: SceneFilter::deny_all(),
+        }
+    }
+}
+
+/// Save a snapshot of the main world as `<capture>.scn.ron` for each saved capture.
+pub(crate) fn write_scene_snapshots(
+    world: &World,
+    mut events: EventReader<CaptureSaved>,
+    filter: Res<CaptureSceneFilter>,
+    registry: Res<AppTypeRegistry>,
+) {
+    for event in events.read() {
+        let scene = DynamicSceneBuilder::from_world(world)
+            .with_component_filter(filter.components.clone())
+            .with_resource_filter(filter.resources.clone())
+            .extract_entities(world.iter_entities().map(|entity| entity.id()))
+            .extract_resources()
+            .remove_empty_entities()
+            .build();
+
+        let path = event.path.with_extension("scn.ron");
+        let written = scene
+            .serialize(&registry.read())
+            .map_err(|e| e.to_string())
+            .and_then(|contents| fs::write(&path, contents).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            warn!("Failed to save scene snapshot to {}: {}", path.display(), e);
+        }
+    }
+}
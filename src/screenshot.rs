@@ -0,0 +1,23 @@
+//! Screenshots saved alongside captures, enabled with the `screenshot` feature.
+
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, save_to_disk};
+
+use crate::CaptureSaved;
+
+/// Take a screenshot of the captured window for each saved capture, saved as a PNG with the same stem as the capture.
+pub(crate) fn screenshot_saved_captures(
+    mut commands: Commands,
+    mut events: EventReader<CaptureSaved>,
+) {
+    for event in events.read() {
+        // Headless captures have no window to take a screenshot of.
+        let Some(window) = event.window else {
+            continue;
+        };
+
+        commands
+            .spawn(Screenshot::window(window))
+            .observe(save_to_disk(event.path.with_extension("png")));
+    }
+}
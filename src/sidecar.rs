@@ -0,0 +1,150 @@
+//! JSON metadata files written next to each capture, enabled with the `sidecar` feature.
+
+use std::fs;
+use std::marker::PhantomData;
+use std::time::UNIX_EPOCH;
+
+use bevy::prelude::*;
+use serde_json::{Map, Value, json};
+
+use crate::CaptureSaved;
+
+/// A source of app-specific metadata recorded in each capture's sidecar file, e.g. the current level or player
+/// position.
+///
+/// Sources are added to the [CaptureMetadataSources] resource.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// # use serde_json::{Value, json};
+/// #[derive(Resource)]
+/// struct CurrentLevel(String);
+///
+/// struct LevelMetadata;
+///
+/// impl CaptureMetadataSource for LevelMetadata {
+///     fn key(&self) -> &str {
+///         "level"
+///     }
+///
+///     fn metadata(&self, world: &World) -> Value {
+///         json!(world.get_resource::<CurrentLevel>().map(|level| &level.0))
+///     }
+/// }
+///
+/// pub fn add_level_metadata(mut sources: ResMut<CaptureMetadataSources>) {
+///     sources.add(LevelMetadata);
+/// }
+/// ```
+pub trait CaptureMetadataSource: Send + Sync + 'static {
+    /// The key the metadata is recorded under in the sidecar's `user` object.
+    fn key(&self) -> &str;
+
+    /// Gather the metadata from the main world once a capture has been saved.
+    fn metadata(&self, world: &World) -> Value;
+}
+
+/// Resource holding the [CaptureMetadataSource]s recorded in each capture's sidecar file.
+#[derive(Resource, Default)]
+pub struct CaptureMetadataSources {
+    sources: Vec<Box<dyn CaptureMetadataSource>>,
+}
+
+impl CaptureMetadataSources {
+    /// Record the metadata of `source` in every following sidecar file.
+    pub fn add(&mut self, source: impl CaptureMetadataSource) {
+        self.sources.push(Box::new(source));
+    }
+}
+
+/// A [CaptureMetadataSource] recording the current value of the state `S`.
+pub struct StateMetadata<S: States> {
+    key: String,
+    state: PhantomData<fn() -> S>,
+}
+
+impl<S: States> StateMetadata<S> {
+    /// Record the current value of `S` under `key`.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            state: PhantomData,
+        }
+    }
+}
+
+impl<S: States> CaptureMetadataSource for StateMetadata<S> {
+    fn key(&self) -> &str {
+        &self.key
+    }
+
+    fn metadata(&self, world: &World) -> Value {
+        match world.get_resource::<State<S>>() {
+            Some(state) => json!(format!("{:?}", state.get())),
+            None => Value::Null,
+        }
+    }
+}
+
+/// Write a `<capture>.json` file next to each saved capture.
+pub(crate) fn write_capture_sidecars(
+    world: &World,
+    mut events: EventReader<CaptureSaved>,
+    sources: Res<CaptureMetadataSources>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform, Option<&Name>)>,
+) {
+    for event in events.read() {
+        let cameras: Vec<Value> = cameras
+            .iter()
+            .map(|(entity, camera, transform, name)| {
+                let (scale, rotation, translation) = transform.to_scale_rotation_translation();
+                json!({
+                    "entity": entity.to_bits(),
+                    "name": name.map(|name| name.as_str()),
+                    "active": camera.is_active,
+                    "order": camera.order,
+                    "translation": translation.to_array(),
+                    "rotation": rotation.to_array(),
+                    "scale": scale.to_array(),
+                })
+            })
+            .collect();
+
+        let user: Map<String, Value> = sources
+            .sources
+            .iter()
+            .map(|source| (source.key().to_owned(), source.metadata(world)))
+            .collect();
+
+        let timestamp = event
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |timestamp| timestamp.as_secs());
+
+        let sidecar = json!({
+            "capture": event.path,
+            "index": event.index,
+            "source": format!("{:?}", event.source),
+            "frame": event.frame,
+            "frame_count": event.frame_count,
+            "timestamp": timestamp,
+            "duration_ms": event.duration.as_secs_f64() * 1000.0,
+            "cameras": cameras,
+            "user": user,
+        });
+
+        let path = event.path.with_extension("json");
+        let written = serde_json::to_string_pretty(&sidecar)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| fs::write(&path, contents).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            warn!(
+                "Failed to write capture metadata to {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! Triggering captures with `SIGUSR1`, e.g. `kill -USR1 <pid>` on a machine without a keyboard attached.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use bevy::prelude::*;
+use signal_hook::consts::SIGUSR1;
+
+use crate::{CaptureSource, RenderDocSystems, RenderDocTrigger};
+
+/// Set by the signal handler when `SIGUSR1` is received.
+#[derive(Resource)]
+pub(crate) struct SignalTrigger {
+    received: Arc<AtomicBool>,
+}
+
+/// Install the `SIGUSR1` handler, warning if it can't be installed.
+pub(crate) fn install(app: &mut App) {
+    let received = Arc::new(AtomicBool::new(false));
+
+    match signal_hook::flag::register(SIGUSR1, received.clone()) {
+        Ok(_) => {
+            app.insert_resource(SignalTrigger { received });
+            app.add_systems(
+                PostUpdate,
+                trigger_on_signal
+                    .run_if(resource_exists::<SignalTrigger>)
+                    .in_set(RenderDocSystems),
+            );
+        }
+        Err(e) => warn!("Failed to install the SIGUSR1 capture handler: {}", e),
+    }
+}
+
+/// Trigger a capture if `SIGUSR1` was received since the last frame.
+fn trigger_on_signal(signal: Res<SignalTrigger>, trigger: Res<RenderDocTrigger>) {
+    if signal.received.swap(false, Ordering::SeqCst) {
+        info!("Received SIGUSR1, triggering capture");
+        trigger.capture_from(CaptureSource::Signal);
+    }
+}
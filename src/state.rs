@@ -0,0 +1,73 @@
+//! Captures taken when entering a state, enabled with the `state` feature.
+
+use bevy::prelude::*;
+
+use bevy::state::state::FreelyMutableState;
+
+use crate::{
+    CaptureSource, PauseWithState, PausedAfterCapture, RenderDocTrigger, pause_after_capture,
+};
+
+/// [App] extension arming a capture whenever a state is entered, e.g. to capture the first frame of a boss fight.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// #[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+/// enum GameState {
+///     #[default]
+///     Exploring,
+///     Boss,
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugins(RenderDocPlugin::default())
+///     .add_renderdoc_capture_on_enter(GameState::Boss)
+///     .add_renderdoc_capture_on_enter_after(GameState::Exploring, 30);
+/// ```
+pub trait RenderDocAppExt {
+    /// Capture the first frame rendered after entering `state`.
+    fn add_renderdoc_capture_on_enter<S: States>(&mut self, state: S) -> &mut Self;
+
+    /// Capture the frame `frames` frames after entering `state`, see [RenderDocTrigger::capture_in].
+    fn add_renderdoc_capture_on_enter_after<S: States>(
+        &mut self,
+        state: S,
+        frames: u32,
+    ) -> &mut Self;
+
+    /// Enter `state` after captures requested with [RenderDocTrigger::capture_and_pause], rather than pausing
+    /// [`Time<Virtual>`].
+    fn add_renderdoc_pause_state<S: FreelyMutableState>(&mut self, state: S) -> &mut Self;
+}
+
+impl RenderDocAppExt for App {
+    fn add_renderdoc_capture_on_enter<S: States>(&mut self, state: S) -> &mut Self {
+        self.add_systems(OnEnter(state), |trigger: Res<RenderDocTrigger>| {
+            trigger.capture_from(CaptureSource::StateEnter);
+        })
+    }
+
+    fn add_renderdoc_capture_on_enter_after<S: States>(
+        &mut self,
+        state: S,
+        frames: u32,
+    ) -> &mut Self {
+        self.add_systems(OnEnter(state), move |trigger: Res<RenderDocTrigger>| {
+            trigger.capture_in(frames);
+        })
+    }
+
+    fn add_renderdoc_pause_state<S: FreelyMutableState>(&mut self, state: S) -> &mut Self {
+        self.insert_resource(PauseWithState).add_systems(
+            First,
+            (move |mut paused: EventReader<PausedAfterCapture>, mut next: ResMut<NextState<S>>| {
+                if paused.read().last().is_some() {
+                    next.set(state.clone());
+                }
+            })
+            .after(pause_after_capture),
+        )
+    }
+}
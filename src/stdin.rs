@@ -0,0 +1,175 @@
+//! Commands read from stdin, for triggering captures from the terminal the app was launched from.
+
+use std::io::BufRead;
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use bevy::prelude::*;
+
+use crate::{CaptureHistory, RenderDocStatus, RenderDocSystems, RenderDocTrigger};
+
+/// Usage printed for unknown commands.
+const HELP: &str = "commands: capture, capture <frames>, status";
+
+/// Lines read from stdin by the reader thread.
+#[derive(Resource)]
+pub(crate) struct StdinCommands {
+    lines: Mutex<Receiver<String>>,
+}
+
+/// Spawn the thread reading commands from stdin.
+pub(crate) fn spawn(app: &mut App) {
+    let (sender, receiver) = mpsc::channel();
+
+    let spawned = std::thread::Builder::new()
+        .name("renderdoc-stdin".into())
+        .spawn(move || {
+            for line in std::io::stdin().lock().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+    match spawned {
+        Ok(_) => {
+            app.insert_resource(StdinCommands {
+                lines: Mutex::new(receiver),
+            });
+            app.add_systems(
+                PostUpdate,
+                run_stdin_commands
+                    .run_if(resource_exists::<StdinCommands>)
+                    .in_set(RenderDocSystems),
+            );
+        }
+        Err(e) => warn!("Failed to spawn the stdin command thread: {}", e),
+    }
+}
+
+/// Run the commands read from stdin since the last frame.
+fn run_stdin_commands(
+    mut commands: Commands,
+    stdin: Res<StdinCommands>,
+    trigger: Res<RenderDocTrigger>,
+    status: Res<RenderDocStatus>,
+    history: Res<CaptureHistory>,
+) {
+    let lines = stdin.lines.lock().unwrap();
+
+    loop {
+        let line = match lines.try_recv() {
+            Ok(line) => line,
+            Err(TryRecvError::Empty) => return,
+            // Stdin was closed, so no more commands will arrive.
+            Err(TryRecvError::Disconnected) => {
+                commands.remove_resource::<StdinCommands>();
+                return;
+            }
+        };
+
+        match parse_command(&line) {
+            Some(StdinCommand::Capture) => {
+                trigger.capture();
+            }
+            Some(StdinCommand::CaptureFrames(frames)) => trigger.capture_frames(frames),
+            Some(StdinCommand::Status) => print_status(&trigger, &status, &history),
+            Some(StdinCommand::Help) => println!("{}", HELP),
+            None => {}
+        }
+    }
+}
+
+/// A command read from stdin.
+#[derive(Debug, PartialEq, Eq)]
+enum StdinCommand {
+    /// `capture`, capturing the next frame.
+    Capture,
+    /// `capture <frames>`, capturing the next `frames` frames.
+    CaptureFrames(u32),
+    /// `status`, printing the capture status.
+    Status,
+    /// Anything else, printing the usage.
+    Help,
+}
+
+/// Parse a line read from stdin, or [None] for an empty line.
+fn parse_command(line: &str) -> Option<StdinCommand> {
+    let mut words = line.split_whitespace();
+    let command = match (words.next(), words.next(), words.next()) {
+        (None, _, _) => return None,
+        (Some("capture"), None, _) => StdinCommand::Capture,
+        (Some("capture"), Some(frames), None) => match frames.parse() {
+            Ok(0) | Err(_) => StdinCommand::Help,
+            Ok(frames) => StdinCommand::CaptureFrames(frames),
+        },
+        (Some("status"), None, _) => StdinCommand::Status,
+        _ => StdinCommand::Help,
+    };
+    Some(command)
+}
+
+fn print_status(trigger: &RenderDocTrigger, status: &RenderDocStatus, history: &CaptureHistory) {
+    if !status.available {
+        println!("RenderDoc is not attached");
+        return;
+    }
+
+    if let Some(backend) = &status.backend {
+        println!("capturing with {}", backend);
+    }
+    if let Some((major, minor, patch)) = status.api_version {
+        println!("RenderDoc API version {}.{}.{}", major, minor, patch);
+    }
+    println!(
+        "captures {}, {} queued",
+        if trigger.is_enabled() {
+            "enabled"
+        } else {
+            "disabled"
+        },
+        trigger.queued_captures()
+    );
+    match history.latest() {
+        Some(capture) => println!(
+            "{} captures saved, latest: {}",
+            history.captures().len(),
+            capture.path.display()
+        ),
+        None => println!("no captures saved"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(parse_command("capture"), Some(StdinCommand::Capture));
+        assert_eq!(parse_command("  capture  "), Some(StdinCommand::Capture));
+        assert_eq!(
+            parse_command("capture 3"),
+            Some(StdinCommand::CaptureFrames(3))
+        );
+        assert_eq!(parse_command("status"), Some(StdinCommand::Status));
+        assert_eq!(parse_command(""), None);
+        assert_eq!(parse_command("   "), None);
+    }
+
+    #[test]
+    fn rejects_invalid_commands() {
+        for line in [
+            "capture 0",
+            "capture many",
+            "capture 1 2",
+            "status now",
+            "quit",
+        ] {
+            assert_eq!(parse_command(line), Some(StdinCommand::Help), "{}", line);
+        }
+    }
+}
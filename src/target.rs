@@ -0,0 +1,74 @@
+//! Device and window handles telling RenderDoc which device and window a capture is for.
+
+use std::ffi::c_void;
+use std::ptr::null;
+
+use bevy::render::renderer::RenderDevice;
+use raw_window_handle::RawWindowHandle;
+
+/// The pointer RenderDoc uses to identify the render device, or null if the graphics backend isn't supported.
+pub(crate) fn device_pointer(device: &RenderDevice) -> *const c_void {
+    let device = device.wgpu_device();
+
+    #[cfg(all(
+        feature = "renderdoc",
+        any(windows, all(unix, not(target_vendor = "apple")))
+    ))]
+    {
+        use ash::vk::Handle;
+        use wgpu::hal::api::Vulkan;
+
+        // SAFETY: The raw instance is only read, and outlives the callback.
+        let pointer = unsafe {
+            device.as_hal::<Vulkan, _, _>(|device| {
+                device.map(|device| {
+                    // RenderDoc identifies Vulkan devices by the dispatch table the instance points to.
+                    let instance = device.shared_instance().raw_instance().handle();
+                    *(instance.as_raw() as *const *const c_void)
+                })
+            })
+        };
+
+        if let Some(pointer) = pointer {
+            return pointer;
+        }
+    }
+
+    #[cfg(all(feature = "renderdoc", windows))]
+    {
+        use wgpu::hal::api::Dx12;
+        use windows_core::Interface;
+
+        // SAFETY: The device is only used to read its raw pointer, which outlives the callback.
+        let pointer = unsafe {
+            device.as_hal::<Dx12, _, _>(|device| {
+                device.map(|device| device.raw_device().as_raw() as *const c_void)
+            })
+        };
+
+        if let Some(pointer) = pointer {
+            return pointer;
+        }
+    }
+
+    // No graphics backend RenderDoc can identify is compiled in on this target, or RenderDoc isn't.
+    #[cfg(not(all(
+        feature = "renderdoc",
+        any(windows, all(unix, not(target_vendor = "apple")))
+    )))]
+    let _ = device;
+
+    null()
+}
+
+/// The native window handle RenderDoc uses to identify a window, or null if the platform isn't supported.
+pub(crate) fn window_handle(window: RawWindowHandle) -> *const c_void {
+    match window {
+        RawWindowHandle::Win32(handle) => handle.hwnd.get() as *const c_void,
+        RawWindowHandle::Xlib(handle) => handle.window as *const c_void,
+        RawWindowHandle::Xcb(handle) => handle.window.get() as usize as *const c_void,
+        RawWindowHandle::Wayland(handle) => handle.surface.as_ptr(),
+        RawWindowHandle::AndroidNdk(handle) => handle.a_native_window.as_ptr(),
+        _ => null(),
+    }
+}
@@ -0,0 +1,443 @@
+//! Helpers for testing how an app triggers captures, in headless apps without RenderDoc or a renderer.
+//!
+//! [RenderDocTestPlugin] stands in for [RenderDocPlugin](crate::RenderDocPlugin), accepting capture requests through
+//! the usual [RenderDocTrigger], events and observers, and taking them with a [MockCaptureBackend] in the main world.
+//! Every capture is recorded in [RequestedCaptures], resolves its [CaptureHandle](crate::CaptureHandle) and sends a
+//! [CaptureSaved] event, so apps can test their custom triggers as well as how they handle saved captures.
+//!
+//! # Example
+//! ```
+//! # use bevy::prelude::*;
+//! # use bevy_renderdoc_capture::*;
+//! # use bevy_renderdoc_capture::test::*;
+//! fn capture_on_fifth_frame(trigger: Res<RenderDocTrigger>, frame: Res<bevy::diagnostic::FrameCount>) {
+//!     if frame.0 == 5 {
+//!         trigger.capture();
+//!     }
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins((MinimalPlugins, RenderDocTestPlugin::default()))
+//!     .add_systems(Update, capture_on_fifth_frame);
+//! for _ in 0..10 {
+//!     app.update();
+//! }
+//! assert_capture_requested(&app, 5);
+//! ```
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    CaptureFailed, CaptureHistory, CaptureRequests, CaptureSaved, CaptureSource, CaptureSuppressed,
+    CaptureTarget, DEFAULT_MAX_QUEUED_CAPTURES, DEFAULT_TRIGGER_COOLDOWN, FrameCaptureBackend,
+    MockCaptureBackend, QueuedCapture, RenderDocStatus, RenderDocTrigger, RequestCapture,
+    capture_on_spawn, capture_on_trigger, count_main_frames, fire_scheduled_captures,
+    handle_capture_requests, send_failed_captures, send_saved_captures, send_suppressed_captures,
+};
+
+/// Stands in for [RenderDocPlugin](crate::RenderDocPlugin) in tests, taking every requested capture with a
+/// [MockCaptureBackend] in the main world, see the [module docs](self).
+///
+/// Captures are taken in [Last] of the frame they're due in, all at once rather than one frame each, and recorded in
+/// [RequestedCaptures]. Span captures are recorded once they start. The capture frame offset and trigger cooldown
+/// apply as they do with [RenderDocPlugin](crate::RenderDocPlugin), and default to the same values.
+pub struct RenderDocTestPlugin {
+    /// See [RenderDocPluginBuilder::capture_frame_offset](crate::RenderDocPluginBuilder::capture_frame_offset).
+    pub capture_frame_offset: u32,
+    /// See [RenderDocPluginBuilder::trigger_cooldown](crate::RenderDocPluginBuilder::trigger_cooldown).
+    pub trigger_cooldown: Duration,
+}
+
+impl Default for RenderDocTestPlugin {
+    fn default() -> Self {
+        Self {
+            capture_frame_offset: 0,
+            trigger_cooldown: DEFAULT_TRIGGER_COOLDOWN,
+        }
+    }
+}
+
+impl Plugin for RenderDocTestPlugin {
+    fn build(&self, app: &mut App) {
+        let requests = Arc::new(CaptureRequests {
+            max_queued: DEFAULT_MAX_QUEUED_CAPTURES,
+            frame_offset: self.capture_frame_offset,
+            trigger_cooldown: self.trigger_cooldown,
+            ..Default::default()
+        });
+        requests.available.store(true, Ordering::SeqCst);
+        requests.enabled.store(true, Ordering::SeqCst);
+
+        let backend = MockCaptureBackend::default();
+        app.insert_resource(RenderDocStatus {
+            backend: Some(backend.name().to_owned()),
+            ..backend.status()
+        })
+        .insert_resource(RenderDocTrigger { requests })
+        .insert_resource(MockCaptures(backend))
+        .init_resource::<RequestedCaptures>()
+        .init_resource::<CaptureHistory>()
+        .add_event::<CaptureSaved>()
+        .add_event::<RequestCapture>()
+        .add_event::<CaptureSuppressed>()
+        .add_event::<CaptureFailed>()
+        .add_observer(capture_on_trigger)
+        .add_observer(capture_on_spawn)
+        .add_systems(
+            First,
+            (
+                count_main_frames,
+                send_saved_captures,
+                send_suppressed_captures,
+                send_failed_captures,
+            )
+                .chain(),
+        )
+        .add_systems(
+            PostUpdate,
+            (
+                handle_capture_requests,
+                fire_scheduled_captures.run_if(resource_exists::<bevy::diagnostic::FrameCount>),
+            ),
+        )
+        .add_systems(Last, take_mock_captures);
+    }
+}
+
+/// A capture requested from [RenderDocTestPlugin].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestedCapture {
+    /// The [FrameCount](bevy::diagnostic::FrameCount) of the captured frame, which is later than the frame the
+    /// capture was requested in with a capture frame offset.
+    pub frame: u32,
+    /// What triggered the capture.
+    pub source: CaptureSource,
+    /// Title requested for the capture, see [RenderDocTrigger::capture_named].
+    pub title: Option<String>,
+    /// Window requested for the capture, see [RenderDocTrigger::capture_window].
+    pub window: Option<Entity>,
+}
+
+/// Resource listing every capture requested from [RenderDocTestPlugin], oldest first.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RequestedCaptures {
+    captures: Vec<RequestedCapture>,
+}
+
+impl RequestedCaptures {
+    /// All captures requested so far, oldest first.
+    pub fn captures(&self) -> &[RequestedCapture] {
+        &self.captures
+    }
+
+    /// The frames captures were requested for, oldest first.
+    pub fn frames(&self) -> Vec<u32> {
+        self.captures.iter().map(|capture| capture.frame).collect()
+    }
+
+    /// Whether a capture was requested for `frame`.
+    pub fn requested_for(&self, frame: u32) -> bool {
+        self.captures.iter().any(|capture| capture.frame == frame)
+    }
+}
+
+/// Assert that a capture was requested for the frame at which [FrameCount](bevy::diagnostic::FrameCount) equals
+/// `frame`, in an app using [RenderDocTestPlugin].
+///
+/// # Panics
+/// If no capture was requested for `frame`, or the app doesn't use [RenderDocTestPlugin].
+#[track_caller]
+pub fn assert_capture_requested(app: &App, frame: u32) {
+    let requested = app.world().resource::<RequestedCaptures>();
+    assert!(
+        requested.requested_for(frame),
+        "expected a capture requested for frame {}, but captures were requested for frames {:?}",
+        frame,
+        requested.frames()
+    );
+}
+
+/// Assert that no capture was requested at all, in an app using [RenderDocTestPlugin].
+///
+/// # Panics
+/// If a capture was requested, or the app doesn't use [RenderDocTestPlugin].
+#[track_caller]
+pub fn assert_no_capture_requested(app: &App) {
+    let requested = app.world().resource::<RequestedCaptures>();
+    assert!(
+        requested.captures().is_empty(),
+        "expected no capture requested, but captures were requested for frames {:?}",
+        requested.frames()
+    );
+}
+
+/// The backend [RenderDocTestPlugin] takes its captures with.
+#[derive(Resource)]
+struct MockCaptures(MockCaptureBackend);
+
+/// Take the captures due this frame with the mock backend, as the render world would once extracting it.
+fn take_mock_captures(
+    trigger: Res<RenderDocTrigger>,
+    mut backend: ResMut<MockCaptures>,
+    mut requested: ResMut<RequestedCaptures>,
+) {
+    let requests = &trigger.requests;
+    // Queued captures are stamped with the number of frames started, one more than the frame's [FrameCount].
+    let started = requests.main_frame.load(Ordering::SeqCst);
+    let frame = started.saturating_sub(1);
+
+    let mut captures = Vec::new();
+    let frames = requests.frames.swap(0, Ordering::SeqCst);
+    let burst_source = *requests.burst_source.lock().unwrap();
+    for offset in 0..frames {
+        captures.push((frame + offset, QueuedCapture::new(default(), burst_source)));
+    }
+    if requests.span_start.swap(false, Ordering::SeqCst) {
+        captures.push((frame, QueuedCapture::new(default(), CaptureSource::Span)));
+    }
+    // Like the render world, only take the queued captures whose frame offset has passed.
+    let mut queue = requests.queue.lock().unwrap();
+    while queue.front().is_some_and(|capture| {
+        capture
+            .requested_frame
+            .saturating_add(requests.frame_offset)
+            <= started
+    }) {
+        captures.extend(queue.pop_front().map(|capture| (frame, capture)));
+    }
+    drop(queue);
+
+    for (frame, capture) in captures {
+        let target = CaptureTarget {
+            window_entity: capture.window,
+            ..default()
+        };
+        backend
+            .0
+            .start_frame_capture(&target, capture.title.as_deref());
+        let file = backend.0.end_frame_capture(&target);
+
+        requested.captures.push(RequestedCapture {
+            frame,
+            source: capture.source,
+            title: capture.title,
+            window: capture.window,
+        });
+
+        let Some(file) = file else {
+            continue;
+        };
+        capture.handle.resolve(Ok(file.path.clone()));
+        requests.saved.lock().unwrap().push(CaptureSaved {
+            index: file.index,
+            path: file.path,
+            frame_count: 1,
+            frame: Some(frame),
+            timestamp: file.timestamp,
+            duration: Duration::ZERO,
+            window: capture.window,
+            source: capture.source,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::diagnostic::FrameCount;
+
+    use super::*;
+
+    /// Run `request` once, on the frame at which [FrameCount] equals `frame`, then update the app past it.
+    fn request_at(frame: u32, request: impl Fn(&RenderDocTrigger) + Send + Sync + 'static) -> App {
+        request_with(RenderDocTestPlugin::default(), frame, request)
+    }
+
+    /// [request_at] with a configured `plugin`.
+    fn request_with(
+        plugin: RenderDocTestPlugin,
+        frame: u32,
+        request: impl Fn(&RenderDocTrigger) + Send + Sync + 'static,
+    ) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, plugin)).add_systems(
+            Update,
+            move |trigger: Res<RenderDocTrigger>, frame_count: Res<FrameCount>| {
+                if frame_count.0 == frame {
+                    request(&trigger);
+                }
+            },
+        );
+        for _ in 0..frame + 5 {
+            app.update();
+        }
+        app
+    }
+
+    #[test]
+    fn stamps_captures_with_their_frame() {
+        let app = request_at(3, |trigger| {
+            trigger.capture();
+        });
+
+        assert_capture_requested(&app, 3);
+        let requested = app.world().resource::<RequestedCaptures>();
+        assert_eq!(requested.frames(), vec![3]);
+        assert_eq!(requested.captures()[0].source, CaptureSource::Manual);
+    }
+
+    #[test]
+    fn records_nothing_without_requests() {
+        let app = request_at(3, |_| {});
+
+        assert_no_capture_requested(&app);
+    }
+
+    #[test]
+    fn records_each_frame_of_a_burst() {
+        let app = request_at(2, |trigger| trigger.capture_frames(3));
+
+        let requested = app.world().resource::<RequestedCaptures>();
+        assert_eq!(requested.frames(), vec![2, 3, 4]);
+        assert!(
+            requested
+                .captures()
+                .iter()
+                .all(|capture| capture.source == CaptureSource::Burst)
+        );
+    }
+
+    #[test]
+    fn records_span_starts() {
+        let app = request_at(4, |trigger| trigger.start_capture());
+
+        let requested = app.world().resource::<RequestedCaptures>();
+        assert_eq!(requested.frames(), vec![4]);
+        assert_eq!(requested.captures()[0].source, CaptureSource::Span);
+    }
+
+    #[test]
+    fn records_titles_and_windows() {
+        let window = Entity::from_raw(42);
+        let app = request_at(1, move |trigger| {
+            trigger.capture_named("Level loaded");
+            trigger.capture_window(window);
+        });
+
+        let requested = app.world().resource::<RequestedCaptures>();
+        assert_eq!(requested.frames(), vec![1, 1]);
+        assert_eq!(
+            requested.captures()[0].title.as_deref(),
+            Some("Level loaded")
+        );
+        assert_eq!(requested.captures()[0].window, None);
+        assert_eq!(requested.captures()[1].title, None);
+        assert_eq!(requested.captures()[1].window, Some(window));
+    }
+
+    #[test]
+    fn saves_captures() {
+        let app = request_at(2, |trigger| {
+            trigger.capture();
+        });
+
+        let history = app.world().resource::<CaptureHistory>();
+        assert_eq!(history.captures().len(), 1);
+        let saved = history.latest().unwrap();
+        assert_eq!(saved.frame, Some(2));
+        assert_eq!(saved.source, CaptureSource::Manual);
+    }
+
+    #[test]
+    fn applies_the_capture_frame_offset() {
+        let plugin = RenderDocTestPlugin {
+            capture_frame_offset: 2,
+            ..default()
+        };
+        let app = request_with(plugin, 3, |trigger| {
+            trigger.capture();
+        });
+
+        assert_capture_requested(&app, 5);
+        let requested = app.world().resource::<RequestedCaptures>();
+        assert_eq!(requested.frames(), vec![5]);
+    }
+
+    /// Suppressed captures collected from [CaptureSuppressed] events.
+    #[derive(Resource, Default)]
+    struct Suppressed(Vec<CaptureSuppressed>);
+
+    /// Trigger two captures from a debounced source on frame 2, collecting the suppressed ones.
+    fn trigger_twice(trigger_cooldown: Duration) -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            RenderDocTestPlugin {
+                trigger_cooldown,
+                ..default()
+            },
+        ))
+        .init_resource::<Suppressed>()
+        .add_systems(
+            Update,
+            |trigger: Res<RenderDocTrigger>,
+             frame_count: Res<FrameCount>,
+             mut events: EventReader<CaptureSuppressed>,
+             mut suppressed: ResMut<Suppressed>| {
+                suppressed.0.extend(events.read().copied());
+                if frame_count.0 == 2 {
+                    trigger.capture_from(CaptureSource::Binding);
+                    trigger.capture_from(CaptureSource::Binding);
+                }
+            },
+        );
+        for _ in 0..5 {
+            app.update();
+        }
+        app
+    }
+
+    #[test]
+    fn suppresses_captures_during_the_trigger_cooldown() {
+        let app = trigger_twice(Duration::from_secs(60));
+
+        let requested = app.world().resource::<RequestedCaptures>();
+        assert_eq!(requested.frames(), vec![2]);
+        assert_eq!(requested.captures()[0].source, CaptureSource::Binding);
+
+        let suppressed = &app.world().resource::<Suppressed>().0;
+        assert_eq!(suppressed.len(), 1);
+        assert_eq!(suppressed[0].source, CaptureSource::Binding);
+        assert!(suppressed[0].remaining > Duration::ZERO);
+        assert!(suppressed[0].remaining <= Duration::from_secs(60));
+    }
+
+    #[test]
+    fn zero_trigger_cooldown_suppresses_nothing() {
+        let app = trigger_twice(Duration::ZERO);
+
+        let requested = app.world().resource::<RequestedCaptures>();
+        assert_eq!(requested.frames(), vec![2, 2]);
+        assert!(app.world().resource::<Suppressed>().0.is_empty());
+    }
+
+    #[test]
+    fn manual_captures_ignore_the_trigger_cooldown() {
+        let plugin = RenderDocTestPlugin {
+            trigger_cooldown: Duration::from_secs(60),
+            ..default()
+        };
+        let app = request_with(plugin, 2, |trigger| {
+            trigger.capture();
+            trigger.capture();
+        });
+
+        assert_eq!(
+            app.world().resource::<RequestedCaptures>().frames(),
+            vec![2, 2]
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//! In-game toasts for saved captures, enabled with the `toast` feature.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::CaptureSaved;
+
+/// How long a toast stays on screen.
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+/// A toast announcing a saved capture, despawned once its timer finishes.
+#[derive(Component)]
+pub(crate) struct CaptureToast {
+    timer: Timer,
+}
+
+/// Show a toast for the most recently saved capture, replacing any toast already shown.
+pub(crate) fn spawn_capture_toasts(
+    mut commands: Commands,
+    mut events: EventReader<CaptureSaved>,
+    toasts: Query<Entity, With<CaptureToast>>,
+) {
+    let Some(event) = events.read().last() else {
+        return;
+    };
+
+    for toast in &toasts {
+        commands.entity(toast).despawn();
+    }
+
+    let message = match event.frame {
+        Some(frame) => format!("Captured frame {} → {}", frame, event.path.display()),
+        None => format!("Captured frame → {}", event.path.display()),
+    };
+
+    commands
+        .spawn((
+            CaptureToast {
+                timer: Timer::new(TOAST_DURATION, TimerMode::Once),
+            },
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(12.0),
+                right: Val::Px(12.0),
+                padding: UiRect::axes(Val::Px(10.0), Val::Px(6.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+            GlobalZIndex(i32::MAX),
+        ))
+        .with_child((
+            Text::new(message),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+        ));
+}
+
+/// Despawn toasts that have been shown for long enough.
+pub(crate) fn despawn_expired_toasts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut toasts: Query<(Entity, &mut CaptureToast)>,
+) {
+    for (entity, mut toast) in &mut toasts {
+        if toast.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Triggering captures with a touch gesture, e.g. on mobile devices without a keyboard.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{CaptureSource, RenderDocTrigger};
+
+/// A multi-finger tap gesture triggering a capture, see [crate::RenderDocPluginBuilder::touch_trigger].
+///
+/// Defaults to a three-finger triple tap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TouchGesture {
+    /// Number of fingers touching the screen at once for a tap to count.
+    pub fingers: usize,
+    /// Number of taps completing the gesture.
+    pub taps: u32,
+    /// Time within which all taps must happen, counted from the first one.
+    pub within: Duration,
+}
+
+impl Default for TouchGesture {
+    fn default() -> Self {
+        Self {
+            fingers: 3,
+            taps: 3,
+            within: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Progress through the [TouchGesture] watched by [trigger_on_touch].
+#[derive(Resource)]
+pub(crate) struct TouchTrigger {
+    gesture: TouchGesture,
+    /// Whether enough fingers are down for the current tap, which counts once.
+    held: bool,
+    taps: u32,
+    first_tap: Duration,
+}
+
+impl TouchTrigger {
+    pub(crate) fn new(gesture: TouchGesture) -> Self {
+        Self {
+            gesture,
+            held: false,
+            taps: 0,
+            first_tap: Duration::ZERO,
+        }
+    }
+}
+
+/// Trigger a capture once the gesture's taps have happened in time.
+pub(crate) fn trigger_on_touch(
+    mut touch: ResMut<TouchTrigger>,
+    touches: Res<Touches>,
+    time: Res<Time<Real>>,
+    trigger: Res<RenderDocTrigger>,
+) {
+    let fingers = touches.iter().count();
+    if fingers == 0 {
+        touch.held = false;
+    }
+    if touch.held || fingers < touch.gesture.fingers {
+        return;
+    }
+    touch.held = true;
+
+    let now = time.elapsed();
+    if touch.taps == 0 || now - touch.first_tap > touch.gesture.within {
+        touch.taps = 0;
+        touch.first_tap = now;
+    }
+    touch.taps += 1;
+
+    if touch.taps >= touch.gesture.taps {
+        touch.taps = 0;
+        info!("Touch gesture detected, triggering capture");
+        trigger.capture_from(CaptureSource::Touch);
+    }
+}
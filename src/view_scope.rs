@@ -0,0 +1,185 @@
+//! Scoping captures to particular cameras, so the passes of an off-screen camera stand out from the main view's.
+
+use bevy::prelude::*;
+use bevy::render::Extract;
+use bevy::render::render_graph::{
+    Node, NodeRunError, RenderGraph, RenderGraphContext, RenderLabel,
+};
+use bevy::render::renderer::RenderContext;
+use bevy::render::sync_world::RenderEntity;
+
+use crate::RenderDocData;
+use crate::debug_groups::{camera_label, capturing};
+
+/// Component marking a camera whose passes are isolated in captures, e.g. a render-to-texture camera being debugged.
+///
+/// While capturing, the camera's view is wrapped in a debug group named `Captured view: ` followed by the camera's
+/// [Name] and render target, and the camera is listed in the capture's comments. With `skip_other_views`, other
+/// cameras don't render the captured frames at all.
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_renderdoc_capture::*;
+/// pub fn spawn_minimap_camera(mut commands: Commands, minimap: Handle<Image>) {
+///     commands.spawn((
+///         Camera {
+///             target: minimap.into(),
+///             ..default()
+///         },
+///         Name::new("Minimap"),
+///         CaptureThisView {
+///             skip_other_views: true,
+///         },
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub struct CaptureThisView {
+    /// Deactivate every other camera on captured frames, reactivating them once the capture is over.
+    pub skip_other_views: bool,
+}
+
+/// Marks a camera deactivated for a capture by [skip_other_views], to be reactivated afterwards.
+#[derive(Component)]
+pub(crate) struct SkippedForCapture;
+
+/// Deactivate the cameras without [CaptureThisView] on captured frames, if a camera asks for it.
+pub(crate) fn skip_other_views(
+    mut commands: Commands,
+    views: Query<&CaptureThisView>,
+    mut cameras: Query<(Entity, &mut Camera), Without<CaptureThisView>>,
+) {
+    if !views.iter().any(|view| view.skip_other_views) {
+        return;
+    }
+
+    for (entity, mut camera) in &mut cameras {
+        // Cameras the app deactivated itself stay deactivated afterwards.
+        if camera.is_active {
+            camera.is_active = false;
+            commands.entity(entity).insert(SkippedForCapture);
+        }
+    }
+}
+
+/// Reactivate the cameras deactivated by [skip_other_views].
+pub(crate) fn restore_other_views(
+    mut commands: Commands,
+    mut cameras: Query<(Entity, &mut Camera), With<SkippedForCapture>>,
+) {
+    for (entity, mut camera) in &mut cameras {
+        camera.is_active = true;
+        commands.entity(entity).remove::<SkippedForCapture>();
+    }
+}
+
+/// Label of a camera with [CaptureThisView], in the render world.
+#[derive(Component)]
+pub(crate) struct CapturedView(String);
+
+/// Mark the render world's cameras with [CaptureThisView] while capturing, unmarking cameras it was removed from, and
+/// list them in the capture's comments.
+pub(crate) fn extract_captured_views(
+    mut commands: Commands,
+    mut renderdoc: ResMut<RenderDocData>,
+    cameras: Extract<Query<(&RenderEntity, &Camera, Option<&Name>)>>,
+    views: Extract<Query<&RenderEntity, With<CaptureThisView>>>,
+) {
+    if !renderdoc.is_capture_active {
+        return;
+    }
+
+    let views: Vec<_> = views.iter().map(RenderEntity::id).collect();
+    let mut labels = Vec::new();
+    for (render_entity, camera, name) in &cameras {
+        let mut entity = commands.entity(render_entity.id());
+        if views.contains(&render_entity.id()) {
+            let label = camera_label(camera, name);
+            labels.push(label.clone());
+            entity.insert(CapturedView(label));
+        } else {
+            entity.remove::<CapturedView>();
+        }
+    }
+    // Multi-frame captures list the cameras of their last frame.
+    renderdoc.active_metadata.views = labels;
+}
+
+#[derive(RenderLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct BeginCapturedView;
+
+#[derive(RenderLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct EndCapturedView;
+
+/// The [CapturedView] a sub graph renders the view of while capturing, if any.
+fn captured_view<'w>(graph: &RenderGraphContext, world: &'w World) -> Option<&'w CapturedView> {
+    if !capturing(world) {
+        return None;
+    }
+    graph
+        .get_view_entity()
+        .and_then(|view| world.get::<CapturedView>(view))
+}
+
+/// Opens a debug group around the view of a camera with [CaptureThisView].
+struct BeginCapturedViewNode;
+
+impl Node for BeginCapturedViewNode {
+    fn run<'w>(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        if let Some(CapturedView(label)) = captured_view(graph, world) {
+            render_context
+                .command_encoder()
+                .push_debug_group(&format!("Captured view: {}", label));
+        }
+        Ok(())
+    }
+}
+
+/// Closes the debug group opened by [BeginCapturedViewNode].
+struct EndCapturedViewNode;
+
+impl Node for EndCapturedViewNode {
+    fn run<'w>(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        if captured_view(graph, world).is_some() {
+            render_context.command_encoder().pop_debug_group();
+        }
+        Ok(())
+    }
+}
+
+/// Run every node of each sub graph of `graph` between a [BeginCapturedViewNode] and an [EndCapturedViewNode].
+fn add_captured_view_groups(graph: &mut RenderGraph) {
+    for (_, sub_graph) in graph.iter_sub_graphs_mut() {
+        add_captured_view_groups(sub_graph);
+
+        let labels: Vec<_> = sub_graph.iter_nodes().map(|state| state.label).collect();
+        if labels.is_empty() {
+            continue;
+        }
+
+        sub_graph.add_node(BeginCapturedView, BeginCapturedViewNode);
+        sub_graph.add_node(EndCapturedView, EndCapturedViewNode);
+        for label in labels {
+            sub_graph.add_node_edge(BeginCapturedView, label);
+            sub_graph.add_node_edge(label, EndCapturedView);
+        }
+    }
+}
+
+/// Add the captured view groups to the render graph's sub graphs, once nodes are wrapped in debug groups so the
+/// captured view's group encloses the camera's own.
+pub(crate) fn add_captured_view_nodes(mut graph: ResMut<RenderGraph>) {
+    add_captured_view_groups(&mut graph);
+}